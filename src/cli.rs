@@ -26,6 +26,17 @@ pub fn cli() -> App<'static, 'static> {
         .hide_env_values(true)
         .help("Control when to use colors on output."),
     )
+    .arg(
+      Arg::with_name("format")
+        .long("format")
+        .env("GER_FORMAT")
+        .global(true)
+        .takes_value(true)
+        .value_name("format")
+        .possible_values(&["text", "json", "yaml", "csv", "tsv"])
+        .hide_env_values(true)
+        .help("Output format of command results."),
+    )
     .arg(
       Arg::with_name("verbose")
         .long("verbose")
@@ -63,6 +74,7 @@ fn configure(args: &ArgMatches) -> Result<CliConfig, failure::Error> {
         false => ColorChoice::Never,
       },
     }),
+    format: args.value_of("format").unwrap_or("text").parse()?,
   };
   Ok(config)
 }
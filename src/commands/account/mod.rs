@@ -0,0 +1,24 @@
+use crate::config::CliConfig;
+use clap::{App, ArgMatches, SubCommand};
+
+mod search;
+mod show;
+
+/// Build the CLI
+pub fn cli() -> App<'static, 'static> {
+  SubCommand::with_name("account")
+    .about("Lists accounts and information about accounts.")
+    .template("{about}\n\nUSAGE:\n    {usage}\n\n{all-args}")
+    .subcommands(vec![show::cli_self(), show::cli_show(), search::cli()])
+}
+
+/// Execute the account command
+pub fn exec(config: &mut CliConfig, args: Option<&ArgMatches>) -> Result<(), failure::Error> {
+  let args = args.unwrap();
+  match args.subcommand() {
+    ("self", subargs) => show::exec(config, subargs, true),
+    ("show", subargs) => show::exec(config, subargs, false),
+    ("search", subargs) => search::exec(config, subargs),
+    _ => Ok(()),
+  }
+}
@@ -0,0 +1,99 @@
+use super::show;
+use crate::config::{CliConfig, OutputFormat};
+use crate::handler::get_remote_restapi_handler;
+use crate::output::{self, Record};
+use crate::util;
+use clap::{App, Arg, ArgMatches, SubCommand};
+use gerlib::accounts::{AccountAdditionalOpt, AccountEndpoints, AccountInfo, AccountQueryParams};
+use std::io::Write;
+use termcolor::{Color, ColorSpec, WriteColor};
+
+pub fn cli() -> App<'static, 'static> {
+  SubCommand::with_name("search")
+    .about("Search accounts, e.g. to find usernames of reviewers.")
+    .template("{about}\n\nUSAGE:\n    {usage}\n\n{all-args}")
+    .setting(clap::AppSettings::DeriveDisplayOrder)
+    .arg(
+      Arg::with_name("query")
+        .required(true)
+        .multiple(true)
+        .help("Account query, e.g. 'name:john' or 'email:john@example.com'. Words are joined by spaces."),
+    )
+    .arg(
+      Arg::with_name("limit")
+        .long("limit")
+        .short("n")
+        .takes_value(true)
+        .value_name("max-count")
+        .validator(util::validate::is_u32)
+        .help("Limit the number of accounts to output."),
+    )
+    .arg(
+      Arg::with_name("start")
+        .long("start")
+        .short("S")
+        .takes_value(true)
+        .value_name("offset")
+        .validator(util::validate::is_u32)
+        .help("Skip the given number of accounts from the beginning of the results."),
+    )
+    .arg(
+      Arg::with_name("remote")
+        .long("remote")
+        .short("r")
+        .takes_value(true)
+        .value_name("NAME")
+        .help("Specify an alternative remote to use."),
+    )
+}
+
+pub fn exec(config: &mut CliConfig, args: Option<&ArgMatches>) -> Result<(), failure::Error> {
+  let args = args.unwrap();
+  let remote = args.value_of("remote");
+  let query: Vec<&str> = args.values_of("query").unwrap().collect();
+
+  let mut rest = get_remote_restapi_handler(config, remote)?;
+  let query_params = AccountQueryParams {
+    query: query.join(" "),
+    additional_opts: Some(vec![AccountAdditionalOpt::Details]),
+    limit: args.value_of("limit").map(|n| n.parse::<u32>().unwrap()),
+    start: args.value_of("start").map(|n| n.parse::<u32>().unwrap()),
+  };
+  let accounts: Vec<AccountInfo> = rest.query_accounts(&query_params)?;
+
+  if config.format != OutputFormat::Text {
+    let records: Vec<Record> = accounts.iter().map(show::record).collect();
+    return output::write_records(config, &records);
+  }
+
+  if accounts.is_empty() {
+    writeln!(config.stdout, "No accounts.")?;
+    return Ok(());
+  }
+
+  let username_maxlen = accounts
+    .iter()
+    .map(|a| a.username.as_ref().map_or(0, |u| u.len()))
+    .max()
+    .unwrap_or(0);
+  let mut stdout = config.stdout.lock();
+  for account in &accounts {
+    stdout.set_color(ColorSpec::new().set_fg(Some(Color::Yellow)))?;
+    write!(stdout, "{:>8}", account.account_id)?;
+    stdout.reset()?;
+    let username = account.username.as_deref().unwrap_or("");
+    write!(
+      stdout,
+      "  {0:1$}  {2}",
+      username,
+      username_maxlen,
+      util::account_name(account)
+    )?;
+    if let Some(email) = &account.email {
+      write!(stdout, " <{}>", email)?;
+    }
+    writeln!(stdout)?;
+  }
+
+  Ok(())
+}
@@ -0,0 +1,195 @@
+use crate::config::{CliConfig, OutputFormat};
+use crate::handler::get_remote_restapi_handler;
+use crate::output::{self, Record};
+use crate::util;
+use clap::{App, Arg, ArgMatches, SubCommand};
+use gerlib::accounts::{AccountEndpoints, AccountInfo, EmailInfo, SshKeyInfo};
+use gerlib::groups::GroupInfo;
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::io::Write;
+use termcolor::{Color, ColorSpec, WriteColor};
+
+pub fn cli_self() -> App<'static, 'static> {
+  cli("self", "Show the account of the remote's credentials.")
+}
+
+pub fn cli_show() -> App<'static, 'static> {
+  cli("show", "Show details of an account.").arg(
+    Arg::with_name("account")
+      .required(true)
+      .help("Account identifier: numeric id, email or username."),
+  )
+}
+
+fn cli(name: &'static str, about: &'static str) -> App<'static, 'static> {
+  SubCommand::with_name(name)
+    .about(about)
+    .template("{about}\n\nUSAGE:\n    {usage}\n\n{all-args}")
+    .setting(clap::AppSettings::DeriveDisplayOrder)
+    .arg(
+      Arg::with_name("remote")
+        .long("remote")
+        .short("r")
+        .takes_value(true)
+        .value_name("NAME")
+        .help("Specify an alternative remote to use."),
+    )
+}
+
+/// Execute the command, showing the account of the remote's credentials if `self_`,
+/// or the account given as argument otherwise
+pub fn exec(config: &mut CliConfig, args: Option<&ArgMatches>, self_: bool) -> Result<(), failure::Error> {
+  let args = args.unwrap();
+  let remote = args.value_of("remote");
+  let account_id = if self_ {
+    "self"
+  } else {
+    args.value_of("account").unwrap()
+  };
+
+  let mut rest = get_remote_restapi_handler(config, remote)?;
+  let account: AccountInfo = rest.get_account(account_id)?;
+  let id = account.account_id.to_string();
+  // Emails and SSH keys of other accounts are only visible to administrators
+  let emails: Result<Vec<EmailInfo>, _> = rest.list_account_emails(&id);
+  let ssh_keys: Result<Vec<SshKeyInfo>, _> = rest.list_ssh_keys(&id);
+  let groups: Result<Vec<GroupInfo>, _> = rest.list_account_groups(&id);
+
+  if config.format != OutputFormat::Text {
+    let mut unavailable = BTreeMap::new();
+    let mut details = |name: &str, value: Result<serde_json::Value, String>| match value {
+      Ok(value) => value,
+      Err(e) => {
+        unavailable.insert(name.to_owned(), e);
+        serde_json::Value::Null
+      }
+    };
+    let emails = details(
+      "emails",
+      emails
+        .as_ref()
+        .map(|emails| emails.iter().map(|e| e.email.as_str()).collect())
+        .map_err(|e| e.to_string()),
+    );
+    let groups = details(
+      "groups",
+      groups
+        .as_ref()
+        .map(|groups| groups.iter().filter_map(|g| g.name.as_deref()).collect())
+        .map_err(|e| e.to_string()),
+    );
+    let ssh_keys = details(
+      "ssh_keys",
+      ssh_keys
+        .as_ref()
+        .map(|ssh_keys| {
+          ssh_keys
+            .iter()
+            .map(|k| {
+              json!({
+                "seq": k.seq,
+                "algorithm": k.algorithm,
+                "comment": k.comment,
+                "valid": k.valid,
+              })
+            })
+            .collect()
+        })
+        .map_err(|e| e.to_string()),
+    );
+    let record = record(&account)
+      .field("emails", emails)
+      .field("groups", groups)
+      .field("ssh_keys", ssh_keys)
+      .field("unavailable", unavailable);
+    return output::write_record(config, &record);
+  }
+
+  let mut stdout = config.stdout.lock();
+  stdout.set_color(ColorSpec::new().set_bold(true))?;
+  write!(stdout, "{}", util::account_name(&account))?;
+  stdout.reset()?;
+  if let Some(username) = &account.username {
+    write!(stdout, " ({})", username)?;
+  }
+  if account.inactive.unwrap_or(false) {
+    stdout.set_color(ColorSpec::new().set_fg(Some(Color::Red)))?;
+    write!(stdout, " Inactive")?;
+    stdout.reset()?;
+  }
+  writeln!(stdout)?;
+  writeln!(stdout, "Account:  {}", account.account_id)?;
+  if let Some(status) = &account.status {
+    writeln!(stdout, "Status:   {}", status)?;
+  }
+  match &emails {
+    Ok(emails) => {
+      for (i, email) in emails.iter().enumerate() {
+        let title = if i == 0 { "Emails:" } else { "" };
+        let preferred = if email.preferred.unwrap_or(false) {
+          " (preferred)"
+        } else {
+          ""
+        };
+        writeln!(stdout, "{:9} {}{}", title, email.email, preferred)?;
+      }
+    }
+    Err(e) => {
+      if let Some(email) = &account.email {
+        writeln!(stdout, "Email:    {}", email)?;
+      }
+      write_unavailable(&mut stdout, "Emails:", e)?;
+    }
+  }
+  match &groups {
+    Ok(groups) => {
+      let names: Vec<&str> = groups.iter().filter_map(|g| g.name.as_deref()).collect();
+      writeln!(stdout, "Groups:   {}", names.join(", "))?;
+    }
+    Err(e) => write_unavailable(&mut stdout, "Groups:", e)?,
+  }
+  match &ssh_keys {
+    Ok(ssh_keys) => {
+      for (i, key) in ssh_keys.iter().enumerate() {
+        let title = if i == 0 { "SSH keys:" } else { "" };
+        write!(stdout, "{:9} {} {}", title, key.seq, key.algorithm)?;
+        if let Some(comment) = &key.comment {
+          write!(stdout, " {}", comment)?;
+        }
+        if !key.valid {
+          stdout.set_color(ColorSpec::new().set_fg(Some(Color::Red)))?;
+          write!(stdout, " (invalid)")?;
+          stdout.reset()?;
+        }
+        writeln!(stdout)?;
+      }
+    }
+    Err(e) => write_unavailable(&mut stdout, "SSH keys:", e)?,
+  }
+
+  Ok(())
+}
+
+/// Write that account details could not be fetched, with the reason (e.g. missing permission)
+fn write_unavailable<W: WriteColor, E: Display>(stdout: &mut W, title: &str, error: E) -> Result<(), failure::Error> {
+  write!(stdout, "{:9} ", title)?;
+  stdout.set_color(ColorSpec::new().set_fg(Some(Color::Yellow)))?;
+  write!(stdout, "unavailable: {}", error)?;
+  stdout.reset()?;
+  writeln!(stdout)?;
+  Ok(())
+}
+
+/// Build the output record of an account
+pub fn record(account: &AccountInfo) -> Record {
+  Record::new()
+    .field("account_id", account.account_id)
+    .field("name", &account.name)
+    .field("username", &account.username)
+    .field("email", &account.email)
+    .field("secondary_emails", &account.secondary_emails)
+    .field("status", &account.status)
+    .field("inactive", account.inactive.unwrap_or(false))
+}
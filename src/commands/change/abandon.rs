@@ -1,6 +1,6 @@
 use super::show;
 use crate::config::CliConfig;
-use crate::handler::get_remote_restapi_handler;
+use crate::handler::{get_remote_restapi_handler, get_remote_ssh_handler};
 use clap::{App, Arg, ArgMatches, SubCommand};
 use gerlib::changes::ChangeEndpoints;
 use gerlib::changes::{AbandonInput, ChangeInfo};
@@ -23,6 +23,14 @@ pub fn cli() -> App<'static, 'static> {
         .takes_value(true)
         .help("Message to be added as review comment to the change when abandoning the change."),
     )
+    .arg(
+      Arg::with_name("notify")
+        .long("notify")
+        .takes_value(true)
+        .value_name("whom")
+        .possible_values(super::NOTIFY_VALUES)
+        .help("Notify handling that defines to whom email notifications should be sent."),
+    )
     .arg(
       Arg::with_name("remote")
         .long("remote")
@@ -38,13 +46,23 @@ pub fn exec(config: &mut CliConfig, args: Option<&ArgMatches>) -> Result<(), fai
   let change_id = args.value_of("change-id").unwrap();
   let message = args.value_of("message");
 
+  if let Some(ssh) = get_remote_ssh_handler(config, remote)? {
+    ssh
+      .review(change_id, message, &[], args.value_of("notify"), true)
+      .map_err(|e| super::action_error("abandon", change_id, e))?;
+    let change: ChangeInfo = ssh.get_change(change_id)?;
+    return show::show(config, &change);
+  }
+
   let mut rest = get_remote_restapi_handler(config, remote)?;
   let abandon_input = AbandonInput {
     message: message.map(|m| m.into()),
-    notify: None,
+    notify: args.value_of("notify").map(super::notify_handling),
     notify_details: None,
   };
-  let change: ChangeInfo = rest.abandon_change(change_id, &abandon_input)?;
+  let change: ChangeInfo = rest
+    .abandon_change(change_id, &abandon_input)
+    .map_err(|e| super::action_error("abandon", change_id, e))?;
 
   show::show(config, &change)?;
 
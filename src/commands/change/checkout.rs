@@ -0,0 +1,179 @@
+use crate::config::{CliConfig, OutputFormat};
+use crate::handler::{get_remote_opts, get_remote_restapi_handler};
+use crate::output::{self, Record};
+use crate::util;
+use clap::{App, Arg, ArgMatches, SubCommand};
+use gerlib::changes::ChangeEndpoints;
+use gerlib::changes::{AdditionalOpt, ChangeInfo};
+use git2::build::CheckoutBuilder;
+use git2::{BranchType, FetchOptions, Oid, Repository};
+use std::io::Write;
+
+pub fn cli() -> App<'static, 'static> {
+  SubCommand::with_name("checkout")
+    .visible_alias("co")
+    .about("Download a change into the local git repository and check it out.")
+    .template("{about}\n\nUSAGE:\n    {usage}\n\n{all-args}")
+    .setting(clap::AppSettings::DeriveDisplayOrder)
+    .arg(Arg::with_name("change-id").required(true).help(
+      "Change identifier. \
+             Can be either a legacy numerical id (e.g. 15813), \
+             full or abbreviated Change-Id (e.g. Ic0ff33) \
+             or commit SHA-1 (e.g. d81b32ef).",
+    ))
+    .arg(
+      Arg::with_name("patchset")
+        .long("patchset")
+        .short("p")
+        .takes_value(true)
+        .value_name("number")
+        .validator(util::validate::is_u32)
+        .help("Patch set to download. Defaults to the current patch set."),
+    )
+    .arg(
+      Arg::with_name("cherry-pick")
+        .long("cherry-pick")
+        .conflicts_with("detach")
+        .help("Cherry-pick the patch set onto the current HEAD instead of creating a new branch."),
+    )
+    .arg(
+      Arg::with_name("detach")
+        .long("detach")
+        .conflicts_with("cherry-pick")
+        .help("Check out the patch set as a detached HEAD instead of creating a new branch."),
+    )
+    .arg(
+      Arg::with_name("remote")
+        .long("remote")
+        .short("r")
+        .takes_value(true)
+        .help("Specify an alternative remote to use."),
+    )
+}
+
+pub fn exec(config: &mut CliConfig, args: Option<&ArgMatches>) -> Result<(), failure::Error> {
+  let args = args.unwrap();
+  let remote = args.value_of("remote");
+  let change_id = args.value_of("change-id").unwrap();
+  let patchset = args.value_of("patchset").map(|n| n.parse::<u32>().unwrap());
+
+  let repo = Repository::discover(std::env::current_dir()?)?;
+  if util::git::is_dirty(&repo)? {
+    return Err(failure::err_msg(
+      "worktree has uncommitted changes, commit or stash them before checking out a change",
+    ));
+  }
+
+  let remote_opts = get_remote_opts(config, remote)?.clone();
+  let mut rest = get_remote_restapi_handler(config, remote)?;
+  let additional_opts = vec![AdditionalOpt::AllRevisions, AdditionalOpt::DownloadCommands];
+  let change: ChangeInfo = rest.get_change(change_id, Some(additional_opts))?;
+
+  let revisions = change
+    .revisions
+    .as_ref()
+    .ok_or_else(|| failure::err_msg(format!("change {} has no revisions", change.number)))?;
+  let (commit_id, revision) = match patchset {
+    Some(number) => revisions.iter().find(|(_, revision)| revision._number == number),
+    None => change
+      .current_revision
+      .as_ref()
+      .and_then(|current| revisions.get_key_value(current)),
+  }
+  .ok_or_else(|| failure::err_msg(format!("no such patch set for change {}", change.number)))?;
+
+  // prefer the fetch information provided by the server, fall back to the project under the remote url
+  let fetch = revision.fetch.as_ref().and_then(|fetch| {
+    ["http", "anonymous http", "ssh"]
+      .iter()
+      .find_map(|scheme| fetch.get(*scheme))
+  });
+  let (url, refname) = match fetch {
+    Some(fetch) => (fetch.url.clone(), fetch.ref_.clone()),
+    None => (
+      format!("{}/{}", remote_opts.url.trim_end_matches('/'), change.project),
+      revision.ref_.clone(),
+    ),
+  };
+
+  util::git::set_ca_bundle(&remote_opts)?;
+  let headers = util::git::remote_headers(&remote_opts)?;
+  let headers: Vec<&str> = headers.iter().map(|h| h.as_str()).collect();
+  let mut fetch_options = FetchOptions::new();
+  fetch_options
+    .remote_callbacks(util::git::remote_callbacks(&remote_opts))
+    .proxy_options(util::git::proxy_options(&remote_opts))
+    .custom_headers(&headers);
+  repo
+    .remote_anonymous(&url)?
+    .fetch(&[refname.as_str()], Some(&mut fetch_options), None)?;
+  let commit = repo.find_commit(Oid::from_str(commit_id)?)?;
+  let patchset = revision._number;
+
+  let (head, branch, message) = if args.is_present("detach") {
+    repo.checkout_tree(commit.as_object(), Some(CheckoutBuilder::new().safe()))?;
+    repo.set_head_detached(commit.id())?;
+    let message = format!(
+      "HEAD is now at {} (change {}, patch set {})",
+      &commit_id[..7],
+      change.number,
+      patchset
+    );
+    (commit.id(), None, message)
+  } else if args.is_present("cherry-pick") {
+    let head = repo.head()?.peel_to_commit()?;
+    repo.cherrypick(&commit, None)?;
+    let mut index = repo.index()?;
+    if index.has_conflicts() {
+      return Err(failure::err_msg(format!(
+        "cherry-pick of change {} patch set {} resulted in conflicts, \
+         resolve them and commit the result",
+        change.number, patchset
+      )));
+    }
+    let tree = repo.find_tree(index.write_tree()?)?;
+    let committer = repo.signature()?;
+    let message = commit.message().unwrap_or_default();
+    let new_commit = repo.commit(Some("HEAD"), &commit.author(), &committer, message, &tree, &[&head])?;
+    repo.cleanup_state()?;
+    let message = format!(
+      "cherry-picked change {} patch set {} as {}",
+      change.number,
+      patchset,
+      &new_commit.to_string()[..7]
+    );
+    (new_commit, None, message)
+  } else {
+    let branch_name = format!("change/{}/{}", change.number, patchset);
+    match repo.find_branch(&branch_name, BranchType::Local) {
+      Ok(branch) => {
+        if branch.get().target() != Some(commit.id()) {
+          return Err(failure::err_msg(format!(
+            "branch '{}' already exists and points to another commit",
+            branch_name
+          )));
+        }
+      }
+      Err(_) => {
+        repo.branch(&branch_name, &commit, false)?;
+      }
+    }
+    repo.checkout_tree(commit.as_object(), Some(CheckoutBuilder::new().safe()))?;
+    repo.set_head(&format!("refs/heads/{}", branch_name))?;
+    let message = format!("Switched to branch '{}'", branch_name);
+    (commit.id(), Some(branch_name), message)
+  };
+
+  if config.format != OutputFormat::Text {
+    let record = Record::new()
+      .field("number", change.number)
+      .field("patch_set", patchset)
+      .field("commit", commit_id)
+      .field("head", head.to_string())
+      .field("branch", branch);
+    return output::write_record(config, &record);
+  }
+  writeln!(config.stdout, "{}", message)?;
+
+  Ok(())
+}
@@ -0,0 +1,95 @@
+use super::show;
+use crate::config::CliConfig;
+use crate::handler::get_remote_restapi_handler;
+use clap::{App, Arg, ArgMatches, SubCommand};
+use gerlib::changes::ChangeEndpoints;
+use gerlib::changes::{ChangeInfo, CherryPickInput};
+
+pub fn cli() -> App<'static, 'static> {
+  SubCommand::with_name("cherry-pick")
+    .about("Cherry-pick the current patch set of a change to another branch.")
+    .template("{about}\n\nUSAGE:\n    {usage}\n\n{all-args}")
+    .setting(clap::AppSettings::DeriveDisplayOrder)
+    .arg(Arg::with_name("change-id").required(true).help(
+      "Change identifier. \
+             Can be either a legacy numerical id (e.g. 15813), \
+             full or abbreviated Change-Id (e.g. Ic0ff33) \
+             or commit SHA-1 (e.g. d81b32ef).",
+    ))
+    .arg(
+      Arg::with_name("destination")
+        .long("destination")
+        .short("d")
+        .takes_value(true)
+        .required(true)
+        .value_name("branch")
+        .help("Destination branch of the cherry-pick."),
+    )
+    .arg(
+      Arg::with_name("message")
+        .long("message")
+        .short("m")
+        .takes_value(true)
+        .help("Commit message for the cherry-pick change. Defaults to the original commit message."),
+    )
+    .arg(
+      Arg::with_name("keep-reviewers")
+        .long("keep-reviewers")
+        .help("Copy the reviewers of the original change to the cherry-pick change."),
+    )
+    .arg(
+      Arg::with_name("allow-conflicts")
+        .long("allow-conflicts")
+        .help("Create the cherry-pick change even if there are conflicts, with conflict markers."),
+    )
+    .arg(
+      Arg::with_name("notify")
+        .long("notify")
+        .takes_value(true)
+        .value_name("whom")
+        .possible_values(super::NOTIFY_VALUES)
+        .help("Notify handling that defines to whom email notifications should be sent."),
+    )
+    .arg(
+      Arg::with_name("remote")
+        .long("remote")
+        .short("r")
+        .takes_value(true)
+        .help("Specify an alternative remote to use."),
+    )
+}
+
+pub fn exec(config: &mut CliConfig, args: Option<&ArgMatches>) -> Result<(), failure::Error> {
+  let args = args.unwrap();
+  let remote = args.value_of("remote");
+  let change_id = args.value_of("change-id").unwrap();
+  let message = args.value_of("message");
+  let destination = args.value_of("destination").unwrap();
+
+  let mut rest = get_remote_restapi_handler(config, remote)?;
+  let cherry_pick_input = CherryPickInput {
+    message: message.map(|m| m.into()),
+    destination: destination.into(),
+    base: None,
+    parent: None,
+    notify: args.value_of("notify").map(super::notify_handling),
+    notify_details: None,
+    keep_reviewers: match args.is_present("keep-reviewers") {
+      true => Some(true),
+      false => None,
+    },
+    allow_conflicts: match args.is_present("allow-conflicts") {
+      true => Some(true),
+      false => None,
+    },
+    topic: None,
+    allow_empty: None,
+  };
+  let change: ChangeInfo = rest
+    .cherry_pick_revision(change_id, "current", &cherry_pick_input)
+    .map_err(|e| super::action_error("cherry-pick", change_id, e))?;
+
+  show::show(config, &change)?;
+
+  Ok(())
+}
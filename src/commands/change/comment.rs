@@ -0,0 +1,163 @@
+use super::review;
+use crate::config::{CliConfig, OutputFormat};
+use crate::handler::get_remote_restapi_handler;
+use crate::output::{self, Record};
+use crate::util;
+use clap::{App, Arg, ArgMatches, SubCommand};
+use gerlib::changes::ChangeEndpoints;
+use gerlib::changes::{CommentInfo, CommentInput};
+use std::io::Write;
+
+pub fn cli() -> App<'static, 'static> {
+  SubCommand::with_name("comment")
+    .about("Write a draft comment on a change.")
+    .template("{about}\n\nUSAGE:\n    {usage}\n\n{all-args}\n\n{after-help}")
+    .after_help(
+      "EXAMPLE:\n    ger change comment 15813 path/to/file.rs:42 -m \"Off by one?\"\n    \
+       ger change comment 15813 --reply-to 2b7bc5bf_3e2dbb4d -m \"Done\" --resolve --publish",
+    )
+    .setting(clap::AppSettings::DeriveDisplayOrder)
+    .arg(Arg::with_name("change-id").required(true).help(
+      "Change identifier. \
+             Can be either a legacy numerical id (e.g. 15813), \
+             full or abbreviated Change-Id (e.g. Ic0ff33) \
+             or commit SHA-1 (e.g. d81b32ef).",
+    ))
+    .arg(Arg::with_name("location").help(
+      "File path to comment on, optionally followed by a line number, e.g. 'path/to/file.rs:42'. \
+             If not specified, the comment is made on the patch set itself.",
+    ))
+    .arg(
+      Arg::with_name("message")
+        .long("message")
+        .short("m")
+        .takes_value(true)
+        .help("Comment message. If not specified, the message is written in the editor."),
+    )
+    .arg(
+      Arg::with_name("reply-to")
+        .long("reply-to")
+        .takes_value(true)
+        .value_name("comment-id")
+        .help("Reply to an existing comment. The location is then taken from that comment."),
+    )
+    .arg(
+      Arg::with_name("resolve")
+        .long("resolve")
+        .help("Mark the comment thread as resolved."),
+    )
+    .arg(
+      Arg::with_name("patchset")
+        .long("patchset")
+        .short("p")
+        .takes_value(true)
+        .value_name("revision")
+        .help("Revision to comment on. Defaults to the current patch set."),
+    )
+    .arg(
+      Arg::with_name("publish")
+        .long("publish")
+        .help("Publish all drafts of the change right after writing this one."),
+    )
+    .arg(
+      Arg::with_name("remote")
+        .long("remote")
+        .short("r")
+        .takes_value(true)
+        .help("Specify an alternative remote to use."),
+    )
+}
+
+pub fn exec(config: &mut CliConfig, args: Option<&ArgMatches>) -> Result<(), failure::Error> {
+  let args = args.unwrap();
+  let remote = args.value_of("remote");
+  let change_id = args.value_of("change-id").unwrap();
+  let reply_to = args.value_of("reply-to");
+  let mut revision = args.value_of("patchset").unwrap_or("current").to_owned();
+
+  let mut rest = get_remote_restapi_handler(config, remote)?;
+
+  let (mut path, mut line) = match args.value_of("location") {
+    Some(location) => parse_location(location),
+    None => ("/PATCHSET_LEVEL".to_owned(), None),
+  };
+
+  if let Some(reply_to) = reply_to {
+    let comments = rest.list_change_comments(change_id)?;
+    let parent: CommentInfo = comments
+      .into_iter()
+      .flat_map(|(path, file_comments)| {
+        file_comments.into_iter().map(move |mut comment| {
+          comment.path.get_or_insert(path.clone());
+          comment
+        })
+      })
+      .find(|comment| comment.id == reply_to)
+      .ok_or_else(|| failure::err_msg(format!("no such comment: {}", reply_to)))?;
+    path = parent.path.unwrap();
+    line = parent.line;
+    if let Some(patch_set) = parent.patch_set {
+      revision = patch_set.to_string();
+    }
+  }
+
+  let message = match args.value_of("message") {
+    Some(m) => m.to_owned(),
+    None => {
+      let location = match line {
+        Some(line) => format!("{}:{}", path, line),
+        None => path.clone(),
+      };
+      let template = format!(
+        "\n{0} Write a comment for change {1} at {2}.\n{0} Lines starting with '{0}' are ignored.\n",
+        util::EDITOR_COMMENT,
+        change_id,
+        location
+      );
+      util::edit_in_editor(&template)?
+    }
+  };
+  if message.is_empty() {
+    return Err(failure::err_msg("aborting comment due to empty message"));
+  }
+
+  let comment_input = CommentInput {
+    id: None,
+    path: Some(path),
+    side: None,
+    line,
+    range: None,
+    in_reply_to: reply_to.map(|r| r.into()),
+    updated: None,
+    message: Some(message),
+    tag: None,
+    unresolved: Some(!args.is_present("resolve")),
+  };
+  let draft: CommentInfo = rest.create_draft(change_id, &revision, &comment_input)?;
+
+  let published = args.is_present("publish");
+  if published {
+    review::publish_drafts(&mut rest, change_id)?;
+  }
+
+  if config.format != OutputFormat::Text {
+    let record = Record::new().field("id", &draft.id).field("published", published);
+    return output::write_record(config, &record);
+  }
+  match published {
+    true => writeln!(config.stdout, "published comment {}", draft.id)?,
+    false => writeln!(config.stdout, "created draft {}", draft.id)?,
+  }
+
+  Ok(())
+}
+
+/// Split a `path[:line]` location into file path and line number
+fn parse_location(location: &str) -> (String, Option<u32>) {
+  if let Some(index) = location.rfind(':') {
+    if let Ok(line) = location[index + 1..].parse::<u32>() {
+      return (location[..index].to_owned(), Some(line));
+    }
+  }
+  (location.to_owned(), None)
+}
@@ -0,0 +1,331 @@
+use super::show;
+use crate::config::{CliConfig, OutputFormat};
+use crate::handler::get_remote_restapi_handler;
+use crate::output::{self, Record};
+use crate::util;
+use clap::{App, Arg, ArgMatches, SubCommand};
+use gerlib::changes::ChangeEndpoints;
+use gerlib::changes::{AdditionalOpt, ChangeInfo, CommentInfo, Side};
+use gerlib::GerritRestApi;
+use std::collections::{BTreeMap, HashMap};
+use std::io::Write;
+use termcolor::{Color, ColorSpec, StandardStreamLock, WriteColor};
+
+/// Build the CLI
+pub fn cli() -> App<'static, 'static> {
+  SubCommand::with_name("comments")
+    .about("Show inline comments of a change.")
+    .template("{about}\n\nUSAGE:\n    {usage}\n\n{all-args}")
+    .setting(clap::AppSettings::DeriveDisplayOrder)
+    .arg(Arg::with_name("change-id").help(
+      "Change identifier. \
+             Can be either a legacy numerical id (e.g. 15813), \
+             full or abbreviated Change-Id (e.g. Ic0ff33) \
+             or commit SHA-1 (e.g. d81b32ef).\n\
+             If not specified, ger will try to retrieve \
+             the Change-id from current directory's git HEAD commit.",
+    ))
+    .arg(
+      Arg::with_name("patchset")
+        .long("patchset")
+        .short("p")
+        .takes_value(true)
+        .value_name("revision")
+        .help(
+          "Only show comments of the given revision. \
+                     Can be either a patch set number or a commit SHA-1. \
+                     Defaults to the comments of all revisions.",
+        ),
+    )
+    .arg(
+      Arg::with_name("unresolved")
+        .long("unresolved")
+        .short("u")
+        .help("Only show unresolved comment threads."),
+    )
+    .arg(
+      Arg::with_name("context")
+        .long("context")
+        .short("C")
+        .takes_value(true)
+        .value_name("lines")
+        .default_value("2")
+        .validator(util::validate::is_u32)
+        .help("Number of code lines to show around the commented line."),
+    )
+    .arg(
+      Arg::with_name("no-pager")
+        .long("no-pager")
+        .help("Output directly to stdout instead of to a pager."),
+    )
+    .arg(
+      Arg::with_name("remote")
+        .long("remote")
+        .short("r")
+        .takes_value(true)
+        .help("Specify an alternative remote to use."),
+    )
+}
+
+/// Execute the command
+pub fn exec(config: &mut CliConfig, args: Option<&ArgMatches>) -> Result<(), failure::Error> {
+  let args = args.unwrap();
+  let remote = args.value_of("remote");
+  let no_pager = args.is_present("no-pager");
+  let unresolved = args.is_present("unresolved");
+  let context = args.value_of("context").unwrap().parse::<usize>()?;
+  let change_id = match args.value_of("change-id") {
+    Some(c) => c.to_string(),
+    None => show::get_change_id_from_git()?,
+  };
+
+  let mut rest = get_remote_restapi_handler(config, remote)?;
+  let comments = match args.value_of("patchset") {
+    Some(revision) => {
+      // revision comments come without their patch set, which is needed to show the code context
+      let patch_set = patch_set_number(&mut rest, &change_id, revision)?;
+      let mut comments = rest.list_revision_comments(&change_id, revision)?;
+      for comment in comments.values_mut().flatten() {
+        comment.patch_set = comment.patch_set.or(Some(patch_set));
+      }
+      comments
+    }
+    None => rest.list_change_comments(&change_id)?,
+  };
+
+  let mut files: BTreeMap<String, Vec<Vec<CommentInfo>>> = BTreeMap::new();
+  for (path, file_comments) in comments {
+    let threads: Vec<Vec<CommentInfo>> = threads(file_comments)
+      .into_iter()
+      .filter(|thread| !unresolved || is_unresolved(thread))
+      .collect();
+    if !threads.is_empty() {
+      files.insert(path, threads);
+    }
+  }
+
+  if files.is_empty() && config.format == OutputFormat::Text {
+    writeln!(config.stdout, "No comments.")?;
+    return Ok(());
+  }
+
+  if !no_pager && config.format == OutputFormat::Text {
+    pager::Pager::new().setup();
+  }
+
+  let mut contents = FileContents::new(&mut rest, &change_id, context);
+  show_comments(config, &files, &mut contents)
+}
+
+/// Get the patch set number of a revision given as either a patch set number or a commit SHA-1
+fn patch_set_number(rest: &mut GerritRestApi, change_id: &str, revision: &str) -> Result<u32, failure::Error> {
+  if let Ok(number) = revision.parse::<u32>() {
+    return Ok(number);
+  }
+  let change: ChangeInfo = rest.get_change(change_id, Some(vec![AdditionalOpt::AllRevisions]))?;
+  change
+    .revisions
+    .as_ref()
+    .and_then(|revisions| revisions.iter().find(|(commit, _)| commit.starts_with(revision)))
+    .map(|(_, revision)| revision._number)
+    .ok_or_else(|| failure::err_msg(format!("no revision {} in change {}", revision, change.number)))
+}
+
+/// Show comment threads grouped by file
+pub fn show_comments(
+  config: &mut CliConfig, files: &BTreeMap<String, Vec<Vec<CommentInfo>>>, contents: &mut FileContents,
+) -> Result<(), failure::Error> {
+  if config.format != OutputFormat::Text {
+    let mut records = Vec::new();
+    for (path, threads) in files {
+      for thread in threads {
+        for comment in thread {
+          records.push(record(path, &thread[0].id, comment));
+        }
+      }
+    }
+    return output::write_records(config, &records);
+  }
+
+  let mut stdout = config.stdout.lock();
+  let mut first = true;
+  for (path, threads) in files {
+    if !first {
+      stdout.write_all(b"\n")?;
+    }
+    first = false;
+
+    stdout.set_color(ColorSpec::new().set_fg(Some(Color::Cyan)).set_bold(true))?;
+    writeln!(stdout, "{}", path)?;
+    stdout.reset()?;
+
+    for thread in threads {
+      show_thread(&mut stdout, path, thread, contents)?;
+    }
+  }
+  Ok(())
+}
+
+/// Show a single comment thread, preceded by the commented code lines
+fn show_thread(
+  stdout: &mut StandardStreamLock, path: &str, thread: &[CommentInfo], contents: &mut FileContents,
+) -> Result<(), failure::Error> {
+  let root = &thread[0];
+
+  stdout.write_all(b"\n")?;
+  match root.line {
+    Some(line) => write!(stdout, "  Line {}", line)?,
+    None => write!(stdout, "  File comment")?,
+  }
+  if let Some(patch_set) = root.patch_set {
+    write!(stdout, " (patch set {})", patch_set)?;
+  }
+  if is_unresolved(thread) {
+    stdout.set_color(ColorSpec::new().set_fg(Some(Color::Red)).set_bold(true))?;
+    write!(stdout, " [unresolved]")?;
+  } else {
+    stdout.set_color(ColorSpec::new().set_fg(Some(Color::Green)))?;
+    write!(stdout, " [resolved]")?;
+  }
+  stdout.reset()?;
+  stdout.write_all(b"\n")?;
+
+  if let (Some(line), Some(patch_set)) = (root.line, root.patch_set) {
+    if !matches!(root.side, Some(Side::Parent)) {
+      let context = contents.context;
+      if let Some(content) = contents.get(patch_set, path)? {
+        show_code_context(stdout, content, line as usize, context)?;
+      }
+    }
+  }
+
+  for comment in thread {
+    stdout.set_color(ColorSpec::new().set_fg(Some(Color::Green)))?;
+    write!(stdout, "  °")?;
+    stdout.reset()?;
+    if let Some(author) = &comment.author {
+      stdout.set_color(ColorSpec::new().set_fg(Some(Color::Black)).set_intense(true))?;
+      write!(stdout, " {}", util::account_name_email(author))?;
+      stdout.reset()?;
+    }
+    stdout.set_color(ColorSpec::new().set_fg(Some(Color::Yellow)))?;
+    write!(stdout, " ({})", util::format_long_datetime(&comment.updated.0))?;
+    stdout.set_color(ColorSpec::new().set_fg(Some(Color::Black)).set_intense(true))?;
+    writeln!(stdout, " [{}]", comment.id)?;
+    stdout.reset()?;
+    if let Some(message) = &comment.message {
+      for line in message.lines() {
+        writeln!(stdout, "    {}", line)?;
+      }
+    }
+  }
+
+  Ok(())
+}
+
+/// Show the code lines surrounding the given line (1-based)
+fn show_code_context(
+  stdout: &mut StandardStreamLock, content: &str, line: usize, context: usize,
+) -> Result<(), failure::Error> {
+  let lines: Vec<&str> = content.lines().collect();
+  if line == 0 || line > lines.len() {
+    return Ok(());
+  }
+  let begin = line.saturating_sub(context).max(1);
+  let end = (line + context).min(lines.len());
+  let number_width = end.to_string().len();
+  for number in begin..=end {
+    if number == line {
+      stdout.set_color(ColorSpec::new().set_bold(true))?;
+      write!(stdout, "  > {0:>1$} | ", number, number_width)?;
+    } else {
+      stdout.set_color(ColorSpec::new().set_fg(Some(Color::Black)).set_intense(true))?;
+      write!(stdout, "    {0:>1$} | ", number, number_width)?;
+    }
+    writeln!(stdout, "{}", lines[number - 1])?;
+    stdout.reset()?;
+  }
+  Ok(())
+}
+
+/// Build the output record of a comment, which belongs to the thread started by `thread_id`
+pub fn record(path: &str, thread_id: &str, comment: &CommentInfo) -> Record {
+  Record::new()
+    .field("path", path)
+    .field("thread", thread_id)
+    .field("id", &comment.id)
+    .field("in_reply_to", &comment.in_reply_to)
+    .field("patch_set", comment.patch_set)
+    .field("line", comment.line)
+    .field("unresolved", comment.unresolved.unwrap_or(false))
+    .field("author", comment.author.as_ref().map(show::account_value))
+    .field("updated", comment.updated.0.to_rfc3339())
+    .field("message", &comment.message)
+}
+
+/// Group comments of a file into threads, ordered by the date of the thread's first comment.
+///
+/// Each thread starts with the comment that is not a reply to any other comment of the list,
+/// followed by all replies in chronological order.
+pub fn threads(mut comments: Vec<CommentInfo>) -> Vec<Vec<CommentInfo>> {
+  comments.sort_by(|a, b| a.updated.0.cmp(&b.updated.0));
+
+  let mut roots: HashMap<String, usize> = HashMap::new();
+  let mut threads: Vec<Vec<CommentInfo>> = Vec::new();
+  for comment in comments {
+    let thread = comment
+      .in_reply_to
+      .as_ref()
+      .and_then(|parent| roots.get(parent).cloned());
+    let thread = match thread {
+      Some(index) => index,
+      None => {
+        threads.push(Vec::new());
+        threads.len() - 1
+      }
+    };
+    roots.insert(comment.id.clone(), thread);
+    threads[thread].push(comment);
+  }
+  threads
+}
+
+/// Check whether a comment thread is unresolved, which is given by its latest comment
+pub fn is_unresolved(thread: &[CommentInfo]) -> bool {
+  thread.last().and_then(|comment| comment.unresolved).unwrap_or(false)
+}
+
+/// Lazy cache of file contents per patch set, used to show code around comments
+pub struct FileContents<'a> {
+  rest: &'a mut GerritRestApi,
+  change_id: &'a str,
+  context: usize,
+  cache: HashMap<(u32, String), String>,
+}
+
+impl<'a> FileContents<'a> {
+  pub fn new(rest: &'a mut GerritRestApi, change_id: &'a str, context: usize) -> Self {
+    Self {
+      rest,
+      change_id,
+      context,
+      cache: HashMap::new(),
+    }
+  }
+
+  /// Get the content of a file in a patch set, or None for magic files or if context is disabled
+  fn get(&mut self, patch_set: u32, path: &str) -> Result<Option<&str>, failure::Error> {
+    if self.context == 0 || path.starts_with('/') {
+      return Ok(None);
+    }
+    let key = (patch_set, path.to_owned());
+    if !self.cache.contains_key(&key) {
+      let content = self
+        .rest
+        .get_content(self.change_id, &patch_set.to_string(), path)
+        .map_err(|e| failure::err_msg(format!("cannot get {} of patch set {}: {}", path, patch_set, e)))?;
+      self.cache.insert(key.clone(), content);
+    }
+    Ok(self.cache.get(&key).map(|content| content.as_str()))
+  }
+}
@@ -1,6 +1,7 @@
 use super::show;
 use crate::config::CliConfig;
 use crate::handler::get_remote_restapi_handler;
+use crate::util;
 use clap::{App, Arg, ArgMatches, SubCommand};
 use gerlib::changes::ChangeEndpoints;
 use gerlib::changes::{ChangeInfo, ChangeInput, ChangeStatus};
@@ -15,8 +16,7 @@ pub fn cli() -> App<'static, 'static> {
         .long("project")
         .short("p")
         .takes_value(true)
-        .required(true)
-        .help("The name of the project."),
+        .help("The name of the project. Defaults to the project of the current git repository."),
     )
     .arg(
       Arg::with_name("branch")
@@ -60,12 +60,18 @@ pub fn cli() -> App<'static, 'static> {
 
 pub fn exec(config: &mut CliConfig, args: Option<&ArgMatches>) -> Result<(), failure::Error> {
   let args = args.unwrap();
-  let remote = args.value_of("remote");
+  let mapped_remote = util::git::current_repo_remote();
+  let remote = args.value_of("remote").or_else(|| mapped_remote.as_deref());
+  let project = match args.value_of("project") {
+    Some(p) => p.to_owned(),
+    None => util::git::current_repo_project()
+      .ok_or_else(|| failure::err_msg("could not figure out the project, please specify --project"))?,
+  };
 
   let mut rest = get_remote_restapi_handler(config, remote)?;
 
   let change_input = ChangeInput {
-    project: args.value_of("project").unwrap().into(),
+    project,
     branch: args.value_of("branch").unwrap().into(),
     subject: args.value_of("subject").unwrap().into(),
     topic: args.value_of("topic").map(|t| t.into()),
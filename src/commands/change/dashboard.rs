@@ -4,8 +4,6 @@ use crate::handler::get_remote_restapi_handler;
 use clap::{App, Arg, ArgMatches, SubCommand};
 use gerlib::changes::ChangeEndpoints;
 use gerlib::changes::{AdditionalOpt, ChangeInfo, QueryParams, QueryStr};
-use std::io::Write;
-use termcolor::{ColorSpec, WriteColor};
 
 /// Build the CLI
 pub fn cli() -> App<'static, 'static> {
@@ -31,6 +29,9 @@ pub fn exec(config: &mut CliConfig, args: Option<&ArgMatches>) -> Result<(), fai
   let args = args.unwrap();
   let remote = args.value_of("remote");
 
+  let mut additional_opts = vec![AdditionalOpt::DetailedAccounts, AdditionalOpt::CurrentRevision];
+  additional_opts.extend(list::template_additional_opts(config, None)?);
+
   let mut rest = get_remote_restapi_handler(config, remote)?;
   let query_param = QueryParams {
     search_queries: Some(vec![
@@ -38,26 +39,18 @@ pub fn exec(config: &mut CliConfig, args: Option<&ArgMatches>) -> Result<(), fai
       QueryStr::Raw("is:open reviewer:self -owner:self".into()),
       QueryStr::Raw("is:closed (owner:self OR reviewer:self) limit:10".into()),
     ]),
-    additional_opts: Some(vec![AdditionalOpt::DetailedAccounts, AdditionalOpt::CurrentRevision]),
+    additional_opts: Some(additional_opts),
     limit: None,
     start: None,
   };
   let changes_vec: Vec<Vec<ChangeInfo>> = rest.query_changes(&query_param)?;
 
-  config.stdout.set_color(ColorSpec::new().set_italic(true).set_bold(true))?;
-  writeln!(config.stdout, "* Outgoing reviews:")?;
-  config.stdout.reset()?;
-  list::list(config, &changes_vec[0])?;
-
-  config.stdout.set_color(ColorSpec::new().set_italic(true).set_bold(true))?;
-  writeln!(config.stdout, "\n* Incoming reviews:")?;
-  config.stdout.reset()?;
-  list::list(config, &changes_vec[1])?;
-
-  config.stdout.set_color(ColorSpec::new().set_italic(true).set_bold(true))?;
-  writeln!(config.stdout, "\n* Recently closed:")?;
-  config.stdout.reset()?;
-  list::list(config, &changes_vec[2])?;
+  let groups = vec![
+    ("Outgoing reviews", &changes_vec[0]),
+    ("Incoming reviews", &changes_vec[1]),
+    ("Recently closed", &changes_vec[2]),
+  ];
+  list::list_groups(config, &groups, None)?;
 
   Ok(())
 }
@@ -0,0 +1,337 @@
+use super::show;
+use crate::config::{CliConfig, OutputFormat};
+use crate::handler::get_remote_restapi_handler;
+use crate::output::{self, Record};
+use crate::util;
+use clap::{App, Arg, ArgMatches, SubCommand};
+use gerlib::changes::ChangeEndpoints;
+use gerlib::changes::{DiffInfo, FileInfo, FileStatus};
+use std::collections::BTreeMap;
+use std::io::Write;
+use termcolor::{Buffer, Color, ColorSpec, WriteColor};
+
+/// Build the CLI
+pub fn cli() -> App<'static, 'static> {
+  SubCommand::with_name("diff")
+    .about("Show the changes introduced by a patch set as unified diff.")
+    .template("{about}\n\nUSAGE:\n    {usage}\n\n{all-args}")
+    .setting(clap::AppSettings::DeriveDisplayOrder)
+    .arg(Arg::with_name("change-id").required(true).help(
+      "Change identifier. \
+             Can be either a legacy numerical id (e.g. 15813), \
+             full or abbreviated Change-Id (e.g. Ic0ff33) \
+             or commit SHA-1 (e.g. d81b32ef).",
+    ))
+    .arg(
+      Arg::with_name("file")
+        .multiple(true)
+        .help("Limit the diff to the given files."),
+    )
+    .arg(
+      Arg::with_name("patchset")
+        .long("patchset")
+        .short("p")
+        .takes_value(true)
+        .value_name("number")
+        .validator(util::validate::is_u32)
+        .help("Patch set to show. Defaults to the current patch set."),
+    )
+    .arg(
+      Arg::with_name("base")
+        .long("base")
+        .short("b")
+        .takes_value(true)
+        .value_name("number")
+        .validator(util::validate::is_u32)
+        .help("Patch set to compare against. Defaults to the parent commit of the patch set."),
+    )
+    .arg(
+      Arg::with_name("stat")
+        .long("stat")
+        .help("Show only a summary of the changed files with their inserted and deleted lines."),
+    )
+    .arg(
+      Arg::with_name("context")
+        .long("unified")
+        .short("U")
+        .takes_value(true)
+        .value_name("lines")
+        .default_value("3")
+        .validator(util::validate::is_u32)
+        .help("Number of context lines to show around each change."),
+    )
+    .arg(
+      Arg::with_name("no-pager")
+        .long("no-pager")
+        .help("Output directly to stdout instead of to a pager."),
+    )
+    .arg(
+      Arg::with_name("remote")
+        .long("remote")
+        .short("r")
+        .takes_value(true)
+        .help("Specify an alternative remote to use."),
+    )
+}
+
+/// Execute the command
+pub fn exec(config: &mut CliConfig, args: Option<&ArgMatches>) -> Result<(), failure::Error> {
+  let args = args.unwrap();
+  let remote = args.value_of("remote");
+  let no_pager = args.is_present("no-pager");
+  let change_id = args.value_of("change-id").unwrap();
+  let revision = args.value_of("patchset").unwrap_or("current");
+  let base = args.value_of("base");
+  let context = args.value_of("context").unwrap().parse::<usize>()?;
+  let filter: Vec<&str> = args.values_of("file").map(|f| f.collect()).unwrap_or_default();
+
+  let mut rest = get_remote_restapi_handler(config, remote)?;
+  let files: BTreeMap<String, FileInfo> = rest
+    .list_files(change_id, revision, base)?
+    .into_iter()
+    .filter(|(path, _)| !path.starts_with('/'))
+    .filter(|(path, _)| {
+      filter.is_empty()
+        || filter
+          .iter()
+          .any(|f| path.as_str() == *f || path.starts_with(&format!("{}/", f)))
+    })
+    .collect();
+
+  if config.format != OutputFormat::Text {
+    let mut records = Vec::new();
+    for (path, file) in &files {
+      let mut record = Record::new()
+        .field("path", path)
+        .field("old_path", &file.old_path)
+        .field("status", show::file_status_initial(&file.status).to_string())
+        .field("lines_inserted", file.lines_inserted)
+        .field("lines_deleted", file.lines_deleted);
+      if !args.is_present("stat") {
+        let diff: DiffInfo = rest.get_diff(change_id, revision, path, base)?;
+        let mut buffer = Buffer::no_color();
+        show_file_diff(&mut buffer, path, file, &diff, context)?;
+        record = record.field("diff", String::from_utf8_lossy(buffer.as_slice()));
+      }
+      records.push(record);
+    }
+    return output::write_records(config, &records);
+  }
+
+  if files.is_empty() {
+    writeln!(config.stdout, "No files.")?;
+    return Ok(());
+  }
+
+  if !no_pager {
+    pager::Pager::new().setup();
+  }
+
+  if args.is_present("stat") {
+    return show_stat(config, &files);
+  }
+
+  let mut stdout = config.stdout.lock();
+  for (path, file) in &files {
+    let diff: DiffInfo = rest.get_diff(change_id, revision, path, base)?;
+    show_file_diff(&mut stdout, path, file, &diff, context)?;
+  }
+
+  Ok(())
+}
+
+/// Show a summary of changed files, similar to `git diff --stat`
+fn show_stat(config: &mut CliConfig, files: &BTreeMap<String, FileInfo>) -> Result<(), failure::Error> {
+  let mut stdout = config.stdout.lock();
+
+  let file_maxlen = files.keys().map(|path| path.len()).max().unwrap_or(0);
+  let lines_max = files
+    .values()
+    .map(|file| file.lines_inserted.unwrap_or(0) + file.lines_deleted.unwrap_or(0))
+    .max()
+    .unwrap_or(0);
+  let count_width = lines_max.to_string().len();
+  let bar_max = match term_size::dimensions_stdout() {
+    Some((w, _)) if w > file_maxlen + count_width + 10 => (w - file_maxlen - count_width - 10).min(50),
+    _ => 40,
+  };
+
+  let mut total_inserted = 0;
+  let mut total_deleted = 0;
+  for (path, file) in files {
+    let inserted = file.lines_inserted.unwrap_or(0);
+    let deleted = file.lines_deleted.unwrap_or(0);
+    total_inserted += inserted;
+    total_deleted += deleted;
+
+    write!(
+      stdout,
+      " {0:1$} | {2:>3$} ",
+      path,
+      file_maxlen,
+      inserted + deleted,
+      count_width
+    )?;
+    let (mut plus, mut minus) = (inserted as usize, deleted as usize);
+    if lines_max as usize > bar_max {
+      plus = (plus * bar_max + lines_max as usize - 1) / lines_max as usize;
+      minus = (minus * bar_max + lines_max as usize - 1) / lines_max as usize;
+    }
+    stdout.set_color(ColorSpec::new().set_fg(Some(Color::Green)))?;
+    write!(stdout, "{}", "+".repeat(plus))?;
+    stdout.set_color(ColorSpec::new().set_fg(Some(Color::Red)))?;
+    write!(stdout, "{}", "-".repeat(minus))?;
+    stdout.reset()?;
+    stdout.write_all(b"\n")?;
+  }
+
+  let file_s = if files.len() > 1 { "s" } else { "" };
+  writeln!(
+    stdout,
+    " {} file{} changed, {} insertions(+), {} deletions(-)",
+    files.len(),
+    file_s,
+    total_inserted,
+    total_deleted
+  )?;
+
+  Ok(())
+}
+
+/// Show the unified diff of a single file
+fn show_file_diff<W: WriteColor>(
+  stdout: &mut W, path: &str, file: &FileInfo, diff: &DiffInfo, context: usize,
+) -> Result<(), failure::Error> {
+  let old_path = file.old_path.as_deref().unwrap_or(path);
+
+  stdout.set_color(ColorSpec::new().set_bold(true))?;
+  writeln!(stdout, "diff --git a/{} b/{}", old_path, path)?;
+  match file.status {
+    FileStatus::Added => writeln!(stdout, "new file")?,
+    FileStatus::Deleted => writeln!(stdout, "deleted file")?,
+    FileStatus::Renamed => writeln!(stdout, "rename from {}\nrename to {}", old_path, path)?,
+    FileStatus::Copied => writeln!(stdout, "copy from {}\ncopy to {}", old_path, path)?,
+    _ => {}
+  }
+  if diff.binary.unwrap_or(false) {
+    writeln!(stdout, "Binary files differ")?;
+    stdout.reset()?;
+    return Ok(());
+  }
+  match file.status {
+    FileStatus::Added => writeln!(stdout, "--- /dev/null")?,
+    _ => writeln!(stdout, "--- a/{}", old_path)?,
+  }
+  match file.status {
+    FileStatus::Deleted => writeln!(stdout, "+++ /dev/null")?,
+    _ => writeln!(stdout, "+++ b/{}", path)?,
+  }
+  stdout.reset()?;
+
+  let lines = diff_lines(diff);
+  for (begin, end) in hunks(&lines, context) {
+    let hunk = &lines[begin..end];
+    let a_count = hunk.iter().filter(|l| l.kind != '+').count();
+    let b_count = hunk.iter().filter(|l| l.kind != '-').count();
+    let a_start = if a_count == 0 { hunk[0].a - 1 } else { hunk[0].a };
+    let b_start = if b_count == 0 { hunk[0].b - 1 } else { hunk[0].b };
+
+    stdout.set_color(ColorSpec::new().set_fg(Some(Color::Cyan)))?;
+    writeln!(stdout, "@@ -{},{} +{},{} @@", a_start, a_count, b_start, b_count)?;
+    for line in hunk {
+      match line.kind {
+        '+' => stdout.set_color(ColorSpec::new().set_fg(Some(Color::Green)))?,
+        '-' => stdout.set_color(ColorSpec::new().set_fg(Some(Color::Red)))?,
+        _ => stdout.reset()?,
+      }
+      writeln!(stdout, "{}{}", line.kind, line.text)?;
+    }
+    stdout.reset()?;
+  }
+
+  Ok(())
+}
+
+/// Single line of a diff, with the line numbers it has in both sides
+struct DiffLine<'a> {
+  /// One of ' ', '-' or '+', or 's' for lines skipped by the server
+  kind: char,
+  text: &'a str,
+  a: usize,
+  b: usize,
+}
+
+/// Flatten the diff content chunks into lines
+fn diff_lines(diff: &DiffInfo) -> Vec<DiffLine> {
+  let mut lines = Vec::new();
+  let (mut a, mut b) = (1, 1);
+  for chunk in &diff.content {
+    if let Some(ab) = &chunk.ab {
+      for text in ab {
+        lines.push(DiffLine { kind: ' ', text, a, b });
+        a += 1;
+        b += 1;
+      }
+    }
+    if let Some(chunk_a) = &chunk.a {
+      for text in chunk_a {
+        lines.push(DiffLine { kind: '-', text, a, b });
+        a += 1;
+      }
+    }
+    if let Some(chunk_b) = &chunk.b {
+      for text in chunk_b {
+        lines.push(DiffLine { kind: '+', text, a, b });
+        b += 1;
+      }
+    }
+    if let Some(skip) = chunk.skip {
+      lines.push(DiffLine {
+        kind: 's',
+        text: "",
+        a,
+        b,
+      });
+      a += skip as usize;
+      b += skip as usize;
+    }
+  }
+  lines
+}
+
+/// Compute the ranges of lines making up each hunk, given the number of context lines
+fn hunks(lines: &[DiffLine], context: usize) -> Vec<(usize, usize)> {
+  let mut hunks: Vec<(usize, usize)> = Vec::new();
+  let mut index = 0;
+  while index < lines.len() {
+    if lines[index].kind != '+' && lines[index].kind != '-' {
+      index += 1;
+      continue;
+    }
+    let mut begin = index;
+    while begin > 0 && index - begin < context && lines[begin - 1].kind == ' ' {
+      begin -= 1;
+    }
+    let mut end = index + 1;
+    let mut unchanged = 0;
+    while end < lines.len() && lines[end].kind != 's' {
+      if lines[end].kind == ' ' {
+        unchanged += 1;
+      } else {
+        unchanged = 0;
+      }
+      end += 1;
+      if unchanged > 2 * context {
+        break;
+      }
+    }
+    let last_change = end - unchanged;
+    end = (last_change + context).min(end);
+    match hunks.last_mut() {
+      Some(last) if last.1 >= begin => last.1 = end,
+      _ => hunks.push((begin, end)),
+    }
+    index = end;
+  }
+  hunks
+}
@@ -0,0 +1,144 @@
+use super::{comments, review};
+use crate::config::{CliConfig, OutputFormat};
+use crate::handler::get_remote_restapi_handler;
+use crate::output::{self, Record};
+use crate::util;
+use clap::{App, Arg, ArgMatches, SubCommand};
+use gerlib::changes::ChangeEndpoints;
+use gerlib::changes::{CommentInfo, CommentInput};
+use std::collections::BTreeMap;
+use std::io::Write;
+
+pub fn cli() -> App<'static, 'static> {
+  SubCommand::with_name("drafts")
+    .about("List, edit, delete or publish draft comments of a change.")
+    .template("{about}\n\nUSAGE:\n    {usage}\n\n{all-args}")
+    .setting(clap::AppSettings::DeriveDisplayOrder)
+    .arg(Arg::with_name("change-id").required(true).help(
+      "Change identifier. \
+             Can be either a legacy numerical id (e.g. 15813), \
+             full or abbreviated Change-Id (e.g. Ic0ff33) \
+             or commit SHA-1 (e.g. d81b32ef).",
+    ))
+    .arg(
+      Arg::with_name("edit")
+        .long("edit")
+        .short("e")
+        .takes_value(true)
+        .value_name("draft-id")
+        .conflicts_with_all(&["delete", "publish"])
+        .help("Edit the message of a draft comment in the editor."),
+    )
+    .arg(
+      Arg::with_name("delete")
+        .long("delete")
+        .short("d")
+        .takes_value(true)
+        .value_name("draft-id")
+        .conflicts_with_all(&["edit", "publish"])
+        .help("Delete a draft comment."),
+    )
+    .arg(
+      Arg::with_name("publish")
+        .long("publish")
+        .conflicts_with_all(&["edit", "delete"])
+        .help("Publish all draft comments of the change."),
+    )
+    .arg(
+      Arg::with_name("no-pager")
+        .long("no-pager")
+        .help("Output directly to stdout instead of to a pager."),
+    )
+    .arg(
+      Arg::with_name("remote")
+        .long("remote")
+        .short("r")
+        .takes_value(true)
+        .help("Specify an alternative remote to use."),
+    )
+}
+
+pub fn exec(config: &mut CliConfig, args: Option<&ArgMatches>) -> Result<(), failure::Error> {
+  let args = args.unwrap();
+  let remote = args.value_of("remote");
+  let change_id = args.value_of("change-id").unwrap();
+
+  let mut rest = get_remote_restapi_handler(config, remote)?;
+
+  if args.is_present("publish") {
+    review::publish_drafts(&mut rest, change_id)?;
+    return action_done(
+      config,
+      "published",
+      change_id,
+      format!("published drafts of change {}", change_id),
+    );
+  }
+
+  let drafts = rest.list_change_drafts(change_id)?;
+
+  if let Some(draft_id) = args.value_of("edit").or(args.value_of("delete")) {
+    let (path, draft) = drafts
+      .into_iter()
+      .flat_map(|(path, file_drafts)| file_drafts.into_iter().map(move |draft| (path.clone(), draft)))
+      .find(|(_, draft)| draft.id == draft_id)
+      .ok_or_else(|| failure::err_msg(format!("no such draft: {}", draft_id)))?;
+    let revision = draft
+      .patch_set
+      .map(|ps| ps.to_string())
+      .unwrap_or_else(|| "current".to_owned());
+
+    if args.is_present("delete") {
+      rest.delete_draft(change_id, &revision, draft_id)?;
+      return action_done(config, "deleted", draft_id, format!("deleted draft {}", draft_id));
+    }
+
+    let message = util::edit_in_editor(draft.message.as_deref().unwrap_or(""))?;
+    if message.is_empty() {
+      return Err(failure::err_msg("aborting edit due to empty message"));
+    }
+    let comment_input = CommentInput {
+      id: Some(draft.id.clone()),
+      path: Some(path),
+      side: draft.side,
+      line: draft.line,
+      range: draft.range,
+      in_reply_to: draft.in_reply_to,
+      updated: None,
+      message: Some(message),
+      tag: draft.tag,
+      unresolved: draft.unresolved,
+    };
+    let draft: CommentInfo = rest.update_draft(change_id, &revision, draft_id, &comment_input)?;
+    return action_done(config, "updated", &draft.id, format!("updated draft {}", draft.id));
+  }
+
+  let mut files: BTreeMap<String, Vec<Vec<CommentInfo>>> = BTreeMap::new();
+  for (path, file_drafts) in drafts {
+    if !file_drafts.is_empty() {
+      files.insert(path, comments::threads(file_drafts));
+    }
+  }
+
+  if files.is_empty() && config.format == OutputFormat::Text {
+    writeln!(config.stdout, "No drafts.")?;
+    return Ok(());
+  }
+
+  if !args.is_present("no-pager") && config.format == OutputFormat::Text {
+    pager::Pager::new().setup();
+  }
+
+  let mut contents = comments::FileContents::new(&mut rest, change_id, 2);
+  comments::show_comments(config, &files, &mut contents)
+}
+
+/// Report an action done on drafts, with `message` as text output
+fn action_done(config: &mut CliConfig, action: &str, id: &str, message: String) -> Result<(), failure::Error> {
+  if config.format != OutputFormat::Text {
+    let record = Record::new().field("id", id).field("action", action);
+    return output::write_record(config, &record);
+  }
+  writeln!(config.stdout, "{}", message)?;
+  Ok(())
+}
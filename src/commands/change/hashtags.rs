@@ -0,0 +1,78 @@
+use crate::config::{CliConfig, OutputFormat};
+use crate::handler::get_remote_restapi_handler;
+use crate::output::{self, Record};
+use clap::{App, Arg, ArgMatches, SubCommand};
+use gerlib::changes::ChangeEndpoints;
+use gerlib::changes::HashtagsInput;
+use std::io::Write;
+
+pub fn cli() -> App<'static, 'static> {
+  SubCommand::with_name("hashtags")
+    .about("Get, add or remove hashtags of changes.")
+    .template("{about}\n\nUSAGE:\n    {usage}\n\n{all-args}")
+    .setting(clap::AppSettings::DeriveDisplayOrder)
+    .arg(Arg::with_name("change-id").required(true).help(
+      "Change identifier. \
+             Can be either a legacy numerical id (e.g. 15813), \
+             full or abbreviated Change-Id (e.g. Ic0ff33) \
+             or commit SHA-1 (e.g. d81b32ef).",
+    ))
+    .arg(
+      Arg::with_name("add")
+        .long("add")
+        .short("a")
+        .takes_value(true)
+        .multiple(true)
+        .number_of_values(1)
+        .value_name("hashtag")
+        .help("Add a hashtag to the change. Can be specified multiple times."),
+    )
+    .arg(
+      Arg::with_name("remove")
+        .long("remove")
+        .short("d")
+        .takes_value(true)
+        .multiple(true)
+        .number_of_values(1)
+        .value_name("hashtag")
+        .help("Remove a hashtag from the change. Can be specified multiple times."),
+    )
+    .arg(
+      Arg::with_name("remote")
+        .long("remote")
+        .short("r")
+        .takes_value(true)
+        .help("Specify an alternative remote to use."),
+    )
+}
+
+pub fn exec(config: &mut CliConfig, args: Option<&ArgMatches>) -> Result<(), failure::Error> {
+  let args = args.unwrap();
+  let remote = args.value_of("remote");
+  let change_id = args.value_of("change-id").unwrap();
+  let add: Option<Vec<String>> = args.values_of("add").map(|v| v.map(|h| h.into()).collect());
+  let remove: Option<Vec<String>> = args.values_of("remove").map(|v| v.map(|h| h.into()).collect());
+
+  let mut rest = get_remote_restapi_handler(config, remote)?;
+
+  let hashtags: Vec<String> = if add.is_some() || remove.is_some() {
+    let hashtags_input = HashtagsInput { add, remove };
+    rest.set_hashtags(change_id, &hashtags_input)?
+  } else {
+    rest.get_hashtags(change_id)?
+  };
+
+  if config.format != OutputFormat::Text {
+    let record = Record::new().field("change", change_id).field("hashtags", &hashtags);
+    return output::write_record(config, &record);
+  }
+
+  if hashtags.is_empty() {
+    writeln!(config.stdout, "No hashtags.")?;
+  }
+  for hashtag in &hashtags {
+    writeln!(config.stdout, "{}", hashtag)?;
+  }
+
+  Ok(())
+}
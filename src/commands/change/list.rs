@@ -1,9 +1,12 @@
-use crate::config::CliConfig;
-use crate::handler::get_remote_restapi_handler;
+use super::show;
+use crate::config::{CliConfig, OutputFormat};
+use crate::handler::{get_remote_restapi_handler, get_remote_ssh_handler};
+use crate::output::{self, Record};
 use crate::util;
+use crate::util::template::Template;
 use clap::{App, Arg, ArgMatches, SubCommand};
 use gerlib::changes::ChangeEndpoints;
-use gerlib::changes::{AdditionalOpt, ChangeInfo, QueryParams};
+use gerlib::changes::{AdditionalOpt, ChangeInfo, QueryParams, QueryStr};
 use std::io::Write;
 use termcolor::{Color, ColorSpec, WriteColor};
 
@@ -13,6 +16,24 @@ pub fn cli() -> App<'static, 'static> {
     .visible_alias("ls")
     .about("Lists changes.")
     .template("{about}\n\nUSAGE:\n    {usage}\n\n{all-args}")
+    .arg(Arg::with_name("query").multiple(true).help(
+      "Search query in gerrit search syntax, e.g. \"status:open project:foo label:Verified=-1\". \
+                 Multiple words are joined into a single query. \
+                 If not specified, the server's default listing is shown.",
+    ))
+    .arg(
+      Arg::with_name("queries")
+        .long("query")
+        .short("q")
+        .takes_value(true)
+        .multiple(true)
+        .number_of_values(1)
+        .value_name("query")
+        .help(
+          "Additional search query. Can be specified multiple times. \
+                     The results of each query are printed as a separate group.",
+        ),
+    )
     .arg(
       Arg::with_name("limit")
         .long("limit")
@@ -25,6 +46,40 @@ pub fn cli() -> App<'static, 'static> {
                      If stdout is not a tty, the default falls back to 25.",
         ),
     )
+    .arg(
+      Arg::with_name("start")
+        .long("start")
+        .short("S")
+        .takes_value(true)
+        .value_name("offset")
+        .validator(util::validate::is_u32)
+        .help("Skip the given number of changes from the beginning of the results."),
+    )
+    .arg(
+      Arg::with_name("option")
+        .long("option")
+        .short("o")
+        .takes_value(true)
+        .multiple(true)
+        .number_of_values(1)
+        .value_name("name")
+        .possible_values(ADDITIONAL_OPTS)
+        .case_insensitive(true)
+        .help("Additional option to be requested for each change. Can be specified multiple times."),
+    )
+    .arg(
+      Arg::with_name("template")
+        .long("template")
+        .short("t")
+        .takes_value(true)
+        .value_name("template")
+        .help(
+          "Format each change with a template, e.g. '{number:>6} {owner.name:<20.20} {labels.Code-Review} {subject}'. \
+                     Fields accept alignment (<, >, ^), width and max width (e.g. {subject:<40.40}), \
+                     and colors are set with directives like {@bold+green} and {@reset}. \
+                     Defaults to the 'list_format' config setting.",
+        ),
+    )
     .arg(
       Arg::with_name("remote")
         .long("remote")
@@ -53,34 +108,217 @@ pub fn exec(config: &mut CliConfig, args: Option<&ArgMatches>) -> Result<(), fai
       None => 25,
     });
 
-  let mut rest = get_remote_restapi_handler(config, remote)?;
-  let query_param = QueryParams {
-    search_queries: None,
-    additional_opts: Some(vec![AdditionalOpt::DetailedAccounts, AdditionalOpt::CurrentRevision]),
-    limit: Some(limit),
-    start: None,
+  let start = args.value_of("start").map(|n| n.parse::<u32>().unwrap());
+
+  let mut queries: Vec<String> = Vec::new();
+  if let Some(words) = args.values_of("query") {
+    queries.push(words.collect::<Vec<_>>().join(" "));
+  }
+  if let Some(values) = args.values_of("queries") {
+    queries.extend(values.map(|q| q.to_owned()));
+  }
+
+  let template = args.value_of("template");
+  let mut additional_opts = vec![AdditionalOpt::DetailedAccounts, AdditionalOpt::CurrentRevision];
+  additional_opts.extend(template_additional_opts(config, template)?);
+  if let Some(values) = args.values_of("option") {
+    for value in values {
+      additional_opts.push(additional_opt_from_str(value).unwrap());
+    }
+  }
+
+  let changes_list: Vec<Vec<ChangeInfo>> = match get_remote_ssh_handler(config, remote)? {
+    Some(ssh) => {
+      if args.is_present("option") {
+        return Err(failure::err_msg("--option is not supported over ssh"));
+      }
+      // gerrit query requires a query
+      let queries = if queries.is_empty() {
+        vec!["status:open".to_owned()]
+      } else {
+        queries.clone()
+      };
+      queries
+        .iter()
+        .map(|q| ssh.query_changes(q, Some(limit), start))
+        .collect::<Result<_, _>>()?
+    }
+    None => {
+      let mut rest = get_remote_restapi_handler(config, remote)?;
+      let query_param = QueryParams {
+        search_queries: match queries.is_empty() {
+          true => None,
+          false => Some(queries.iter().map(|q| QueryStr::Raw(q.clone())).collect()),
+        },
+        additional_opts: Some(additional_opts),
+        limit: Some(limit),
+        start,
+      };
+      rest.query_changes(&query_param)?
+    }
   };
-  let changes_list: Vec<Vec<ChangeInfo>> = rest.query_changes(&query_param)?;
 
   if changes_list.is_empty() {
-    writeln!(config.stdout, "No changes.")?;
+    return list(config, &Vec::new(), template);
+  }
+  if queries.len() <= 1 {
+    for changes in &changes_list {
+      list(config, changes, template)?;
+    }
     return Ok(());
   }
-  for changes in &changes_list {
-    list(config, changes)?;
+  let groups: Vec<(&str, &Vec<ChangeInfo>)> = queries.iter().map(|q| q.as_str()).zip(changes_list.iter()).collect();
+  list_groups(config, &groups, template)
+}
+
+/// Get the template to format changes with, from the argument or the `list_format` setting
+fn get_template(config: &CliConfig, template: Option<&str>) -> Result<Option<Template>, failure::Error> {
+  match template.or(config.user.settings.list_format.as_deref()) {
+    Some(template) => Ok(Some(Template::parse(template)?)),
+    None => Ok(None),
+  }
+}
+
+/// Additional options to be requested so that the fields used in the template are available
+pub fn template_additional_opts(
+  config: &CliConfig, template: Option<&str>,
+) -> Result<Vec<AdditionalOpt>, failure::Error> {
+  let mut additional_opts = Vec::new();
+  if let Some(template) = get_template(config, template)? {
+    let fields = template.fields();
+    if fields.iter().any(|f| f.starts_with("labels")) {
+      additional_opts.push(AdditionalOpt::DetailedLabels);
+    }
+    if fields.iter().any(|f| f.starts_with("message")) {
+      additional_opts.push(AdditionalOpt::CurrentCommit);
+    }
+  }
+  Ok(additional_opts)
+}
+
+/// Get the value of a template field for a change
+fn template_value(change: &ChangeInfo, record: &Record, name: &str) -> Option<String> {
+  match name {
+    "commit" => change.current_revision.as_ref().map(|r| r[..7].to_owned()),
+    "time" => Some(util::format_short_datetime(&change.updated.0)),
+    "owner" => change.owner.name.clone().or(change.owner.username.clone()),
+    "wip" if change.work_in_progress => Some("WIP".to_owned()),
+    "wip" => None,
+    _ => record.get(name).map(output::value_to_string),
+  }
+}
+
+/// Additional option names accepted by `--option`, as named in the gerrit REST API
+static ADDITIONAL_OPTS: &[&str] = &[
+  "LABELS",
+  "DETAILED_LABELS",
+  "CURRENT_REVISION",
+  "ALL_REVISIONS",
+  "DOWNLOAD_COMMANDS",
+  "DRAFT_COMMENTS",
+  "CURRENT_COMMIT",
+  "ALL_COMMITS",
+  "CURRENT_FILES",
+  "ALL_FILES",
+  "DETAILED_ACCOUNTS",
+  "REVIEWER_UPDATES",
+  "MESSAGES",
+  "CURRENT_ACTIONS",
+  "CHANGE_ACTIONS",
+  "REVIEWED",
+  "SKIP_MERGEABLE",
+  "SUBMITTABLE",
+  "WEB_LINKS",
+  "CHECK",
+  "COMMIT_FOOTERS",
+  "PUSH_CERTIFICATES",
+  "TRACKING_IDS",
+];
+
+/// Convert an additional option name into its gerlib counterpart
+fn additional_opt_from_str(name: &str) -> Option<AdditionalOpt> {
+  let opt = match name.to_uppercase().as_str() {
+    "LABELS" => AdditionalOpt::Labels,
+    "DETAILED_LABELS" => AdditionalOpt::DetailedLabels,
+    "CURRENT_REVISION" => AdditionalOpt::CurrentRevision,
+    "ALL_REVISIONS" => AdditionalOpt::AllRevisions,
+    "DOWNLOAD_COMMANDS" => AdditionalOpt::DownloadCommands,
+    "DRAFT_COMMENTS" => AdditionalOpt::DraftComments,
+    "CURRENT_COMMIT" => AdditionalOpt::CurrentCommit,
+    "ALL_COMMITS" => AdditionalOpt::AllCommits,
+    "CURRENT_FILES" => AdditionalOpt::CurrentFiles,
+    "ALL_FILES" => AdditionalOpt::AllFiles,
+    "DETAILED_ACCOUNTS" => AdditionalOpt::DetailedAccounts,
+    "REVIEWER_UPDATES" => AdditionalOpt::ReviewerUpdates,
+    "MESSAGES" => AdditionalOpt::Messages,
+    "CURRENT_ACTIONS" => AdditionalOpt::CurrentActions,
+    "CHANGE_ACTIONS" => AdditionalOpt::ChangeActions,
+    "REVIEWED" => AdditionalOpt::Reviewed,
+    "SKIP_MERGEABLE" => AdditionalOpt::SkipMergeable,
+    "SUBMITTABLE" => AdditionalOpt::Submittable,
+    "WEB_LINKS" => AdditionalOpt::WebLinks,
+    "CHECK" => AdditionalOpt::Check,
+    "COMMIT_FOOTERS" => AdditionalOpt::CommitFooters,
+    "PUSH_CERTIFICATES" => AdditionalOpt::PushCertificates,
+    "TRACKING_IDS" => AdditionalOpt::TrackingIds,
+    _ => return None,
+  };
+  Some(opt)
+}
+
+/// Show groups of changes, each under a title.
+///
+/// For machine-readable formats, the title is given in the `group` field of each change record.
+pub fn list_groups(
+  config: &mut CliConfig, groups: &[(&str, &Vec<ChangeInfo>)], template: Option<&str>,
+) -> Result<(), failure::Error> {
+  if config.format != OutputFormat::Text {
+    let mut records = Vec::new();
+    for (title, changes) in groups {
+      for change in changes.iter() {
+        let mut record = Record::new().field("group", title);
+        record.extend(show::record(change));
+        records.push(record);
+      }
+    }
+    return output::write_records(config, &records);
   }
 
+  for (i, (title, changes)) in groups.iter().enumerate() {
+    config
+      .stdout
+      .set_color(ColorSpec::new().set_italic(true).set_bold(true))?;
+    writeln!(config.stdout, "{}* {}:", if i > 0 { "\n" } else { "" }, title)?;
+    config.stdout.reset()?;
+    list(config, changes, template)?;
+  }
   Ok(())
 }
 
-/// Show list of changes
-pub fn list(config: &mut CliConfig, changes: &Vec<ChangeInfo>) -> Result<(), failure::Error> {
+/// Show list of changes, formatted with the given template or the `list_format` setting if any
+pub fn list(config: &mut CliConfig, changes: &Vec<ChangeInfo>, template: Option<&str>) -> Result<(), failure::Error> {
+  if config.format != OutputFormat::Text {
+    let records: Vec<Record> = changes.iter().map(show::record).collect();
+    return output::write_records(config, &records);
+  }
+
   if changes.is_empty() {
     writeln!(config.stdout, "No changes.")?;
     return Ok(());
   }
 
+  let template = get_template(config, template)?;
   let mut stdout = config.stdout.lock();
+
+  if let Some(template) = template {
+    for change in changes {
+      let record = show::record(change);
+      template.render(&mut stdout, |name| template_value(change, &record, name))?;
+      stdout.write_all(b"\n")?;
+    }
+    return Ok(());
+  }
+
   for change in changes {
     stdout.reset()?;
 
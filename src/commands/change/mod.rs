@@ -1,13 +1,29 @@
 use crate::config::CliConfig;
 use clap::{App, ArgMatches, SubCommand};
+use gerlib::changes::NotifyHandling;
 
 mod abandon;
+mod checkout;
+mod cherry_pick;
+mod comment;
+mod comments;
 mod create;
 mod dashboard;
+mod diff;
+mod drafts;
+mod hashtags;
 mod list;
+mod move_;
+mod private;
+mod rebase;
+mod restore;
+mod revert;
+mod review;
+mod reviewers;
 mod show;
 mod submit;
 mod topic;
+mod wip;
 
 /// Build the CLI
 pub fn cli() -> App<'static, 'static> {
@@ -19,9 +35,26 @@ pub fn cli() -> App<'static, 'static> {
       create::cli(),
       list::cli(),
       show::cli(),
+      diff::cli(),
+      checkout::cli(),
+      review::cli(),
+      comments::cli(),
+      comment::cli(),
+      drafts::cli(),
+      reviewers::cli(),
       topic::cli(),
+      hashtags::cli(),
+      wip::cli_wip(),
+      wip::cli_ready(),
+      private::cli_private(),
+      private::cli_unprivate(),
       submit::cli(),
       abandon::cli(),
+      restore::cli(),
+      rebase::cli(),
+      revert::cli(),
+      cherry_pick::cli(),
+      move_::cli(),
     ])
 }
 
@@ -37,9 +70,65 @@ pub fn exec(config: &mut CliConfig, args: Option<&ArgMatches>) -> Result<(), fai
     ("create", subargs) => create::exec(config, subargs),
     ("list", subargs) => list::exec(config, subargs),
     ("show", subargs) => show::exec(config, subargs),
+    ("diff", subargs) => diff::exec(config, subargs),
+    ("checkout", subargs) => checkout::exec(config, subargs),
+    ("review", subargs) => review::exec(config, subargs),
+    ("comments", subargs) => comments::exec(config, subargs),
+    ("comment", subargs) => comment::exec(config, subargs),
+    ("drafts", subargs) => drafts::exec(config, subargs),
+    ("reviewers", subargs) => reviewers::exec(config, subargs),
     ("topic", subargs) => topic::exec(config, subargs),
+    ("hashtags", subargs) => hashtags::exec(config, subargs),
+    ("wip", subargs) => wip::exec(config, subargs, true),
+    ("ready", subargs) => wip::exec(config, subargs, false),
+    ("private", subargs) => private::exec(config, subargs, true),
+    ("unprivate", subargs) => private::exec(config, subargs, false),
     ("submit", subargs) => submit::exec(config, subargs),
     ("abandon", subargs) => abandon::exec(config, subargs),
+    ("restore", subargs) => restore::exec(config, subargs),
+    ("rebase", subargs) => rebase::exec(config, subargs),
+    ("revert", subargs) => revert::exec(config, subargs),
+    ("cherry-pick", subargs) => cherry_pick::exec(config, subargs),
+    ("move", subargs) => move_::exec(config, subargs),
     _ => Ok(()),
   }
 }
+
+/// Notify handling values accepted by `--notify` options
+static NOTIFY_VALUES: &[&str] = &["none", "owner", "owner_reviewers", "all"];
+
+/// Convert a `--notify` value into gerlib's notify handling
+fn notify_handling(value: &str) -> NotifyHandling {
+  match value {
+    "none" => NotifyHandling::None,
+    "owner" => NotifyHandling::Owner,
+    "owner_reviewers" => NotifyHandling::OwnerReviewers,
+    _ => NotifyHandling::All,
+  }
+}
+
+/// Turn the failure of a change action into a readable error.
+///
+/// Conflicts (HTTP 409) are reported with the reason given by the server. gerlib does not expose the
+/// status of a failed request, so they are recognized by the status or its name in the error message.
+fn action_error<E: Into<failure::Error>>(action: &str, change_id: &str, error: E) -> failure::Error {
+  let message = error.into().to_string();
+  let reason = message
+    .lines()
+    .map(|l| l.trim())
+    .filter(|l| !l.is_empty())
+    .last()
+    .unwrap_or("unknown error");
+  if is_conflict(&message) {
+    failure::err_msg(format!("cannot {} change {}: conflict: {}", action, change_id, reason))
+  } else {
+    failure::err_msg(format!("cannot {} change {}: {}", action, change_id, reason))
+  }
+}
+
+/// Whether an error message tells of an HTTP 409 Conflict response.
+fn is_conflict(message: &str) -> bool {
+  message
+    .split(|c: char| !c.is_ascii_alphanumeric())
+    .any(|word| word == "409" || word.eq_ignore_ascii_case("conflict"))
+}
@@ -0,0 +1,75 @@
+use crate::config::{CliConfig, OutputFormat};
+use crate::handler::get_remote_restapi_handler;
+use crate::output::{self, Record};
+use clap::{App, Arg, ArgMatches, SubCommand};
+use gerlib::changes::ChangeEndpoints;
+use gerlib::changes::{ChangeInfo, PrivateInput};
+use std::io::Write;
+
+pub fn cli_private() -> App<'static, 'static> {
+  cli("private", "Mark a change as private.")
+}
+
+pub fn cli_unprivate() -> App<'static, 'static> {
+  cli("unprivate", "Unmark a change as private.")
+}
+
+fn cli(name: &'static str, about: &'static str) -> App<'static, 'static> {
+  SubCommand::with_name(name)
+    .about(about)
+    .template("{about}\n\nUSAGE:\n    {usage}\n\n{all-args}")
+    .setting(clap::AppSettings::DeriveDisplayOrder)
+    .arg(Arg::with_name("change-id").required(true).help(
+      "Change identifier. \
+             Can be either a legacy numerical id (e.g. 15813), \
+             full or abbreviated Change-Id (e.g. Ic0ff33) \
+             or commit SHA-1 (e.g. d81b32ef).",
+    ))
+    .arg(
+      Arg::with_name("message")
+        .long("message")
+        .short("m")
+        .takes_value(true)
+        .help("Message to be added as review comment to the change."),
+    )
+    .arg(
+      Arg::with_name("remote")
+        .long("remote")
+        .short("r")
+        .takes_value(true)
+        .help("Specify an alternative remote to use."),
+    )
+}
+
+/// Execute the command, marking the change as private if `private`, or unmarking it otherwise
+pub fn exec(config: &mut CliConfig, args: Option<&ArgMatches>, private: bool) -> Result<(), failure::Error> {
+  let args = args.unwrap();
+  let remote = args.value_of("remote");
+  let change_id = args.value_of("change-id").unwrap();
+  let message = args.value_of("message");
+
+  let mut rest = get_remote_restapi_handler(config, remote)?;
+  let private_input = PrivateInput {
+    message: message.map(|m| m.into()),
+  };
+  if private {
+    rest.mark_private(change_id, &private_input)?;
+  } else {
+    rest.unmark_private(change_id, &private_input)?;
+  }
+
+  let change: ChangeInfo = rest.get_change(change_id, None)?;
+  if config.format != OutputFormat::Text {
+    let record = Record::new()
+      .field("number", change.number)
+      .field("private", change.is_private);
+    return output::write_record(config, &record);
+  }
+  let state = match change.is_private {
+    true => "private",
+    false => "not private",
+  };
+  writeln!(config.stdout, "Change {} is {}", change.number, state)?;
+
+  Ok(())
+}
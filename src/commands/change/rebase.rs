@@ -0,0 +1,56 @@
+use super::show;
+use crate::config::CliConfig;
+use crate::handler::get_remote_restapi_handler;
+use clap::{App, Arg, ArgMatches, SubCommand};
+use gerlib::changes::ChangeEndpoints;
+use gerlib::changes::{ChangeInfo, RebaseInput};
+
+pub fn cli() -> App<'static, 'static> {
+  SubCommand::with_name("rebase")
+    .about("Rebase a change.")
+    .template("{about}\n\nUSAGE:\n    {usage}\n\n{all-args}")
+    .setting(clap::AppSettings::DeriveDisplayOrder)
+    .arg(Arg::with_name("change-id").required(true).help(
+      "Change identifier. \
+             Can be either a legacy numerical id (e.g. 15813), \
+             full or abbreviated Change-Id (e.g. Ic0ff33) \
+             or commit SHA-1 (e.g. d81b32ef).",
+    ))
+    .arg(
+      Arg::with_name("base")
+        .long("base")
+        .short("b")
+        .takes_value(true)
+        .value_name("commit|change")
+        .help(
+          "The new parent revision. Can be a commit SHA-1 or a change number. \
+                     Defaults to the tip of the destination branch.",
+        ),
+    )
+    .arg(
+      Arg::with_name("remote")
+        .long("remote")
+        .short("r")
+        .takes_value(true)
+        .help("Specify an alternative remote to use."),
+    )
+}
+
+pub fn exec(config: &mut CliConfig, args: Option<&ArgMatches>) -> Result<(), failure::Error> {
+  let args = args.unwrap();
+  let remote = args.value_of("remote");
+  let change_id = args.value_of("change-id").unwrap();
+  let base = args.value_of("base");
+
+  let mut rest = get_remote_restapi_handler(config, remote)?;
+  let rebase_input = RebaseInput {
+    base: base.map(|b| b.into()),
+  };
+  let change: ChangeInfo = rest
+    .rebase_change(change_id, &rebase_input)
+    .map_err(|e| super::action_error("rebase", change_id, e))?;
+
+  show::show(config, &change)?;
+
+  Ok(())
+}
@@ -0,0 +1,153 @@
+use super::show;
+use crate::config::CliConfig;
+use crate::handler::{get_remote_restapi_handler, get_remote_ssh_handler};
+use crate::util;
+use clap::{App, Arg, ArgMatches, SubCommand};
+use gerlib::changes::ChangeEndpoints;
+use gerlib::changes::{ChangeInfo, DraftHandling, ReviewInput, ReviewResult};
+use gerlib::GerritRestApi;
+
+pub fn cli() -> App<'static, 'static> {
+  SubCommand::with_name("review")
+    .about("Vote on labels and post a review message to a change.")
+    .template("{about}\n\nUSAGE:\n    {usage}\n\n{all-args}\n\n{after-help}")
+    .after_help("EXAMPLE:\n    ger change review 15813 -l Code-Review=+2 -l Verified=+1 -m \"Looks good\"")
+    .setting(clap::AppSettings::DeriveDisplayOrder)
+    .arg(Arg::with_name("change-id").help(
+      "Change identifier. \
+             Can be either a legacy numerical id (e.g. 15813), \
+             full or abbreviated Change-Id (e.g. Ic0ff33) \
+             or commit SHA-1 (e.g. d81b32ef).\n\
+             If not specified, ger will try to retrieve \
+             the Change-id from current directory's git HEAD commit.",
+    ))
+    .arg(
+      Arg::with_name("label")
+        .long("label")
+        .short("l")
+        .takes_value(true)
+        .multiple(true)
+        .number_of_values(1)
+        .value_name("label=vote")
+        .validator(util::validate::is_label_vote)
+        .help("Vote on a label, e.g. 'Code-Review=+2'. Can be specified multiple times."),
+    )
+    .arg(
+      Arg::with_name("message")
+        .long("message")
+        .short("m")
+        .takes_value(true)
+        .help("Message to be added as review comment to the change."),
+    )
+    .arg(
+      Arg::with_name("notify")
+        .long("notify")
+        .takes_value(true)
+        .value_name("whom")
+        .possible_values(super::NOTIFY_VALUES)
+        .help("Notify handling that defines to whom email notifications should be sent."),
+    )
+    .arg(
+      Arg::with_name("ready")
+        .long("ready")
+        .conflicts_with("wip")
+        .help("Mark the change as ready for review."),
+    )
+    .arg(
+      Arg::with_name("wip")
+        .long("wip")
+        .conflicts_with("ready")
+        .help("Mark the change as work in progress."),
+    )
+    .arg(
+      Arg::with_name("publish-drafts")
+        .long("publish-drafts")
+        .help("Publish all draft comments of the change along with the review."),
+    )
+    .arg(
+      Arg::with_name("remote")
+        .long("remote")
+        .short("r")
+        .takes_value(true)
+        .help("Specify an alternative remote to use."),
+    )
+}
+
+pub fn exec(config: &mut CliConfig, args: Option<&ArgMatches>) -> Result<(), failure::Error> {
+  let args = args.unwrap();
+  let remote = args.value_of("remote");
+  let message = args.value_of("message");
+  let change_id = match args.value_of("change-id") {
+    Some(c) => c.to_string(),
+    None => show::get_change_id_from_git()?,
+  };
+
+  let mut labels = Vec::new();
+  if let Some(values) = args.values_of("label") {
+    for value in values {
+      let (name, vote) = value.split_at(value.find('=').unwrap());
+      labels.push((name.to_owned(), vote[1..].trim().parse::<i32>()?));
+    }
+  }
+
+  if let Some(ssh) = get_remote_ssh_handler(config, remote)? {
+    if args.is_present("publish-drafts") || args.is_present("ready") || args.is_present("wip") {
+      return Err(failure::err_msg(
+        "--publish-drafts, --ready and --wip are not supported over ssh",
+      ));
+    }
+    let labels: Vec<&str> = args.values_of("label").map(|v| v.collect()).unwrap_or_default();
+    ssh.review(&change_id, message, &labels, args.value_of("notify"), false)?;
+    let change: ChangeInfo = ssh.get_change(&change_id)?;
+    return show::show(config, &change);
+  }
+
+  let mut rest = get_remote_restapi_handler(config, remote)?;
+  let mut review_input = empty_review_input();
+  review_input.message = message.map(|m| m.into());
+  if !labels.is_empty() {
+    review_input.labels = Some(labels.into_iter().collect());
+  }
+  review_input.notify = args.value_of("notify").map(super::notify_handling);
+  if args.is_present("publish-drafts") {
+    review_input.drafts = Some(DraftHandling::PublishAllRevisions);
+  }
+  if args.is_present("ready") {
+    review_input.ready = Some(true);
+  }
+  if args.is_present("wip") {
+    review_input.work_in_progress = Some(true);
+  }
+  let _review: ReviewResult = rest.set_review(&change_id, "current", &review_input)?;
+
+  let change: ChangeInfo = rest.get_change(&change_id, Some(show::additional_opts()))?;
+  show::show(config, &change)?;
+
+  Ok(())
+}
+
+/// Review input which does not change anything, to be filled by the caller
+pub fn empty_review_input() -> ReviewInput {
+  ReviewInput {
+    message: None,
+    tag: None,
+    labels: None,
+    comments: None,
+    robot_comments: None,
+    drafts: None,
+    notify: None,
+    notify_details: None,
+    omit_duplicate_comments: None,
+    on_behalf_of: None,
+    reviewers: None,
+    ready: None,
+    work_in_progress: None,
+  }
+}
+
+/// Publish all draft comments of a change through the review endpoint
+pub fn publish_drafts(rest: &mut GerritRestApi, change_id: &str) -> Result<ReviewResult, failure::Error> {
+  let mut review_input = empty_review_input();
+  review_input.drafts = Some(DraftHandling::PublishAllRevisions);
+  Ok(rest.set_review(change_id, "current", &review_input)?)
+}
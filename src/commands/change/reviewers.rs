@@ -0,0 +1,220 @@
+use super::show;
+use crate::config::{CliConfig, OutputFormat};
+use crate::handler::get_remote_restapi_handler;
+use crate::output::{self, Record};
+use crate::util;
+use clap::{App, Arg, ArgMatches, SubCommand};
+use gerlib::changes::ChangeEndpoints;
+use gerlib::changes::{AddReviewerResult, ReviewerInfo, ReviewerInput, ReviewerState};
+use gerlib::GerritRestApi;
+use std::io::Write;
+use termcolor::{Color, ColorSpec, WriteColor};
+
+pub fn cli() -> App<'static, 'static> {
+  SubCommand::with_name("reviewers")
+    .about("List, add or remove reviewers and CCs of a change.")
+    .template("{about}\n\nUSAGE:\n    {usage}\n\n{all-args}")
+    .setting(clap::AppSettings::DeriveDisplayOrder)
+    .arg(Arg::with_name("change-id").required(true).help(
+      "Change identifier. \
+             Can be either a legacy numerical id (e.g. 15813), \
+             full or abbreviated Change-Id (e.g. Ic0ff33) \
+             or commit SHA-1 (e.g. d81b32ef).",
+    ))
+    .arg(
+      Arg::with_name("add")
+        .long("add")
+        .short("a")
+        .takes_value(true)
+        .multiple(true)
+        .number_of_values(1)
+        .value_name("user|group")
+        .help("Add a user or group as reviewer. Can be specified multiple times."),
+    )
+    .arg(
+      Arg::with_name("cc")
+        .long("cc")
+        .takes_value(true)
+        .multiple(true)
+        .number_of_values(1)
+        .value_name("user|group")
+        .help("Add a user or group to CC. Can be specified multiple times."),
+    )
+    .arg(
+      Arg::with_name("remove")
+        .long("remove")
+        .short("d")
+        .takes_value(true)
+        .multiple(true)
+        .number_of_values(1)
+        .value_name("user")
+        .help("Remove a reviewer or CC. Can be specified multiple times."),
+    )
+    .arg(
+      Arg::with_name("yes")
+        .long("yes")
+        .short("y")
+        .help("Do not ask for confirmation when adding large groups."),
+    )
+    .arg(
+      Arg::with_name("notify")
+        .long("notify")
+        .takes_value(true)
+        .value_name("whom")
+        .possible_values(super::NOTIFY_VALUES)
+        .help("Notify handling that defines to whom email notifications should be sent."),
+    )
+    .arg(
+      Arg::with_name("remote")
+        .long("remote")
+        .short("r")
+        .takes_value(true)
+        .help("Specify an alternative remote to use."),
+    )
+}
+
+pub fn exec(config: &mut CliConfig, args: Option<&ArgMatches>) -> Result<(), failure::Error> {
+  let args = args.unwrap();
+  let remote = args.value_of("remote");
+  let change_id = args.value_of("change-id").unwrap();
+  let yes = args.is_present("yes");
+  let notify = args.value_of("notify");
+
+  let mut rest = get_remote_restapi_handler(config, remote)?;
+
+  let text = config.format == OutputFormat::Text;
+  let mut modified = false;
+  if let Some(reviewers) = args.values_of("add") {
+    for reviewer in reviewers {
+      add_reviewer(
+        config,
+        &mut rest,
+        change_id,
+        reviewer,
+        ReviewerState::Reviewer,
+        notify,
+        yes,
+      )?;
+    }
+    modified = true;
+  }
+  if let Some(ccs) = args.values_of("cc") {
+    for cc in ccs {
+      add_reviewer(config, &mut rest, change_id, cc, ReviewerState::Cc, notify, yes)?;
+    }
+    modified = true;
+  }
+  if let Some(reviewers) = args.values_of("remove") {
+    for reviewer in reviewers {
+      rest.delete_reviewer(change_id, reviewer)?;
+      if text {
+        writeln!(config.stdout, "removed {}", reviewer)?;
+      }
+    }
+    modified = true;
+  }
+  if modified && text {
+    config.stdout.write_all(b"\n")?;
+  }
+
+  let reviewers: Vec<ReviewerInfo> = rest.list_reviewers(change_id)?;
+  show_reviewers(config, &reviewers)
+}
+
+/// Add a reviewer or CC to a change, asking for confirmation if the server requires it
+fn add_reviewer(
+  config: &mut CliConfig, rest: &mut GerritRestApi, change_id: &str, reviewer: &str, state: ReviewerState,
+  notify: Option<&str>, yes: bool,
+) -> Result<(), failure::Error> {
+  let mut reviewer_input = ReviewerInput {
+    reviewer: reviewer.into(),
+    state: Some(state),
+    confirmed: if yes { Some(true) } else { None },
+    notify: notify.map(super::notify_handling),
+    notify_details: None,
+  };
+  let mut result: AddReviewerResult = rest.add_reviewer(change_id, &reviewer_input)?;
+
+  if result.confirm.unwrap_or(false) {
+    if config.format != OutputFormat::Text {
+      return Err(failure::err_msg(format!(
+        "adding '{}' needs confirmation, use --yes to add large groups with machine-readable output",
+        reviewer
+      )));
+    }
+    let question = result
+      .error
+      .clone()
+      .unwrap_or_else(|| format!("Do you want to add all members of '{}'?", reviewer));
+    let mut stderr = std::io::stderr();
+    if !util::prompt_confirm(&mut stderr, &question)? {
+      writeln!(stderr, "skipped {}", reviewer)?;
+      return Ok(());
+    }
+    reviewer_input.confirmed = Some(true);
+    result = rest.add_reviewer(change_id, &reviewer_input)?;
+  }
+
+  if let Some(error) = &result.error {
+    return Err(failure::err_msg(format!("failed to add '{}': {}", reviewer, error)));
+  }
+
+  if config.format != OutputFormat::Text {
+    return Ok(());
+  }
+  let added = result.reviewers.iter().chain(result.ccs.iter()).flatten();
+  for reviewer in added {
+    writeln!(config.stdout, "added {}", util::account_name_email(&reviewer.account))?;
+  }
+  Ok(())
+}
+
+/// Show reviewers with their votes
+pub fn show_reviewers(config: &mut CliConfig, reviewers: &[ReviewerInfo]) -> Result<(), failure::Error> {
+  if config.format != OutputFormat::Text {
+    let records: Vec<Record> = reviewers
+      .iter()
+      .map(|r| {
+        Record::new()
+          .field("account", show::account_value(&r.account))
+          .field("approvals", &r.approvals)
+      })
+      .collect();
+    return output::write_records(config, &records);
+  }
+
+  let mut stdout = config.stdout.lock();
+
+  if reviewers.is_empty() {
+    writeln!(stdout, "No reviewers.")?;
+    return Ok(());
+  }
+
+  let names: Vec<String> = reviewers.iter().map(|r| util::account_name_email(&r.account)).collect();
+  let name_maxlen = names.iter().map(|n| n.len()).max().unwrap_or(0);
+
+  for (reviewer, name) in reviewers.iter().zip(names.iter()) {
+    write!(stdout, "{}", name)?;
+    if let Some(approvals) = &reviewer.approvals {
+      let padding = name_maxlen - name.len();
+      write!(stdout, "{0:1$}", "", padding)?;
+      for (label, value) in approvals {
+        let vote: i32 = value.trim().parse().unwrap_or(0);
+        if vote == 0 {
+          continue;
+        }
+        write!(stdout, "  {}: ", label)?;
+        if vote > 0 {
+          stdout.set_color(ColorSpec::new().set_fg(Some(Color::Green)))?;
+        } else {
+          stdout.set_color(ColorSpec::new().set_fg(Some(Color::Red)))?;
+        }
+        write!(stdout, "{:+}", vote)?;
+        stdout.reset()?;
+      }
+    }
+    stdout.write_all(b"\n")?;
+  }
+
+  Ok(())
+}
@@ -1,10 +1,13 @@
-use crate::config::CliConfig;
+use crate::config::{CliConfig, OutputFormat};
 use crate::handler::get_remote_restapi_handler;
+use crate::output::{self, Record};
 use crate::util;
 use clap::{App, Arg, ArgMatches, SubCommand};
+use gerlib::accounts::AccountInfo;
 use gerlib::changes::{AdditionalOpt, ChangeInfo, FileStatus};
 use gerlib::changes::{ChangeEndpoints, ChangeMessageInfo};
 use git2::Repository;
+use serde_json::{json, Map, Value};
 use std::io::Write;
 use termcolor::{Color, ColorSpec, StandardStreamLock, WriteColor};
 
@@ -64,21 +67,29 @@ pub fn exec(config: &mut CliConfig, args: Option<&ArgMatches>) -> Result<(), fai
     let change: ChangeInfo = rest.get_change(&change_id, Some(additional_opts))?;
     show_messages(config, change.messages.as_ref().unwrap())?;
   } else {
-    let additional_opts = vec![
-      AdditionalOpt::CurrentRevision,
-      AdditionalOpt::CurrentCommit,
-      AdditionalOpt::CurrentFiles,
-      AdditionalOpt::DetailedAccounts,
-      AdditionalOpt::DetailedLabels,
-    ];
-    let change: ChangeInfo = rest.get_change(&change_id, Some(additional_opts))?;
+    let change: ChangeInfo = rest.get_change(&change_id, Some(additional_opts()))?;
     show(config, &change)?;
   }
 
   Ok(())
 }
 
+/// Additional options to request for a change in order to show it
+pub fn additional_opts() -> Vec<AdditionalOpt> {
+  vec![
+    AdditionalOpt::CurrentRevision,
+    AdditionalOpt::CurrentCommit,
+    AdditionalOpt::CurrentFiles,
+    AdditionalOpt::DetailedAccounts,
+    AdditionalOpt::DetailedLabels,
+  ]
+}
+
 pub fn show(config: &mut CliConfig, change: &ChangeInfo) -> Result<(), failure::Error> {
+  if config.format != OutputFormat::Text {
+    return output::write_record(config, &record(change));
+  }
+
   let mut stdout = config.stdout.lock();
 
   stdout.set_color(ColorSpec::new().set_fg(Some(Color::Yellow)))?;
@@ -89,6 +100,10 @@ pub fn show(config: &mut CliConfig, change: &ChangeInfo) -> Result<(), failure::
   if change.work_in_progress {
     write!(stdout, " (WIP)")?;
   }
+  if change.is_private {
+    stdout.set_color(ColorSpec::new().set_fg(Some(Color::Magenta)).set_bold(true))?;
+    write!(stdout, " (Private)")?;
+  }
 
   stdout.reset()?;
 
@@ -139,6 +154,12 @@ pub fn show(config: &mut CliConfig, change: &ChangeInfo) -> Result<(), failure::
     writeln!(stdout, "Topic:       {}", topic)?;
   }
 
+  if let Some(hashtags) = &change.hashtags {
+    if !hashtags.is_empty() {
+      writeln!(stdout, "Hashtags:    {}", hashtags.join(", "))?;
+    }
+  }
+
   if let Some(current_commit) = current_commit {
     if let Some(author) = &current_commit.author {
       writeln!(stdout, "Author:      {} <{}>", author.name, author.email)?;
@@ -332,6 +353,11 @@ pub fn show(config: &mut CliConfig, change: &ChangeInfo) -> Result<(), failure::
 }
 
 pub fn show_messages(config: &mut CliConfig, messages: &Vec<ChangeMessageInfo>) -> Result<(), failure::Error> {
+  if config.format != OutputFormat::Text {
+    let records: Vec<Record> = messages.iter().map(message_record).collect();
+    return output::write_records(config, &records);
+  }
+
   let mut stdout = config.stdout.lock();
 
   if messages.is_empty() {
@@ -367,7 +393,98 @@ pub fn show_messages(config: &mut CliConfig, messages: &Vec<ChangeMessageInfo>)
   Ok(())
 }
 
-fn file_status_initial(status: &FileStatus) -> char {
+/// Build the output record of a change.
+///
+/// Fields are always present, with null values when the information was not requested.
+pub fn record(change: &ChangeInfo) -> Record {
+  let current_revision_info = change.revisions.as_ref().and_then(|revisions| {
+    change
+      .current_revision
+      .as_ref()
+      .and_then(|current| revisions.get(current))
+  });
+  let current_commit = current_revision_info.and_then(|info| info.commit.as_ref());
+
+  let labels = change.labels.as_ref().map(|labels| {
+    let mut votes = Map::new();
+    for (name, label) in labels {
+      let values: Vec<i32> = label
+        .all
+        .iter()
+        .flatten()
+        .filter_map(|approval| approval.value)
+        .filter(|value| *value != 0)
+        .collect();
+      // a rejecting vote takes precedence over approvals, like in gerrit
+      let vote = match values.iter().min() {
+        Some(min) if *min < 0 => Some(*min),
+        _ => values.iter().max().cloned(),
+      };
+      votes.insert(
+        name.clone(),
+        vote.map(|v| json!(format!("{:+}", v))).unwrap_or(Value::Null),
+      );
+    }
+    Value::Object(votes)
+  });
+
+  let files = current_revision_info.and_then(|info| info.files.as_ref()).map(|files| {
+    let mut map = Map::new();
+    for (path, file) in files {
+      let file = json!({
+        "status": file_status_initial(&file.status).to_string(),
+        "lines_inserted": file.lines_inserted,
+        "lines_deleted": file.lines_deleted,
+      });
+      map.insert(path.clone(), file);
+    }
+    Value::Object(map)
+  });
+
+  Record::new()
+    .field("number", change.number)
+    .field("change_id", change.change_id.clone())
+    .field("project", change.project.clone())
+    .field("branch", change.branch.clone())
+    .field("topic", change.topic.clone())
+    .field("hashtags", change.hashtags.clone())
+    .field("subject", change.subject.clone())
+    .field("status", change.status.to_string())
+    .field("work_in_progress", change.work_in_progress)
+    .field("private", change.is_private)
+    .field("owner", account_value(&change.owner))
+    .field("created", change.created.0.to_rfc3339())
+    .field("updated", change.updated.0.to_rfc3339())
+    .field("current_revision", change.current_revision.clone())
+    .field("patch_set", current_revision_info.map(|info| info._number))
+    .field("submit_type", change.submit_type.as_ref().map(|s| s.to_string()))
+    .field("total_comment_count", change.total_comment_count)
+    .field("unresolved_comment_count", change.unresolved_comment_count)
+    .field("labels", labels)
+    .field("message", current_commit.and_then(|commit| commit.message.clone()))
+    .field("files", files)
+}
+
+/// Build the output record of a change message
+pub fn message_record(message: &ChangeMessageInfo) -> Record {
+  Record::new()
+    .field("id", message.id.clone())
+    .field("author", message.author.as_ref().map(account_value))
+    .field("date", message.date.0.to_rfc3339())
+    .field("message", message.message.clone())
+}
+
+/// Build the output value of an account
+pub fn account_value(account: &AccountInfo) -> Value {
+  json!({
+    "account_id": account.account_id,
+    "name": account.name,
+    "email": account.email,
+    "username": account.username,
+  })
+}
+
+pub fn file_status_initial(status: &FileStatus) -> char {
   match status {
     FileStatus::Added => 'A',
     FileStatus::Modified => 'M',
@@ -378,7 +495,7 @@ fn file_status_initial(status: &FileStatus) -> char {
   }
 }
 
-fn get_change_id_from_git() -> Result<String, failure::Error> {
+pub fn get_change_id_from_git() -> Result<String, failure::Error> {
   let repo = Repository::discover(std::env::current_dir()?)?;
   let head = repo.head()?;
   let commit = head.peel_to_commit()?;
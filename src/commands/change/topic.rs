@@ -1,5 +1,6 @@
-use crate::config::CliConfig;
-use crate::handler::get_remote_restapi_handler;
+use crate::config::{CliConfig, OutputFormat};
+use crate::handler::{get_remote_restapi_handler, get_remote_ssh_handler};
+use crate::output::{self, Record};
 use clap::{App, Arg, ArgMatches, SubCommand};
 use gerlib::changes::ChangeEndpoints;
 use gerlib::changes::TopicInput;
@@ -46,18 +47,34 @@ pub fn exec(config: &mut CliConfig, args: Option<&ArgMatches>) -> Result<(), fai
   let remote = args.value_of("remote");
   let change_id = args.value_of("change-id").unwrap();
 
-  let mut rest = get_remote_restapi_handler(config, remote)?;
-
-  let topic_res = if let Some(topic) = args.value_of("set") {
-    let topic = TopicInput { topic: topic.into() };
-    Some(rest.set_topic(change_id, &topic)?)
-  } else if args.is_present("delete") {
-    rest.delete_topic(change_id)?;
-    None
+  let topic_res = if let Some(ssh) = get_remote_ssh_handler(config, remote)? {
+    if let Some(topic) = args.value_of("set") {
+      ssh.set_topic(change_id, topic)?;
+      Some(topic.to_owned())
+    } else if args.is_present("delete") {
+      ssh.set_topic(change_id, "")?;
+      None
+    } else {
+      Some(ssh.get_change(change_id)?.topic.unwrap_or_default())
+    }
   } else {
-    Some(rest.get_topic(change_id)?)
+    let mut rest = get_remote_restapi_handler(config, remote)?;
+    if let Some(topic) = args.value_of("set") {
+      let topic = TopicInput { topic: topic.into() };
+      Some(rest.set_topic(change_id, &topic)?)
+    } else if args.is_present("delete") {
+      rest.delete_topic(change_id)?;
+      None
+    } else {
+      Some(rest.get_topic(change_id)?)
+    }
   };
 
+  if config.format != OutputFormat::Text {
+    let record = Record::new().field("change", change_id).field("topic", &topic_res);
+    return output::write_record(config, &record);
+  }
+
   if let Some(topic) = &topic_res {
     writeln!(config.stdout, "{}", topic)?;
   }
@@ -0,0 +1,75 @@
+use crate::config::{CliConfig, OutputFormat};
+use crate::handler::get_remote_restapi_handler;
+use crate::output::{self, Record};
+use clap::{App, Arg, ArgMatches, SubCommand};
+use gerlib::changes::ChangeEndpoints;
+use gerlib::changes::{ChangeInfo, WorkInProgressInput};
+use std::io::Write;
+
+pub fn cli_wip() -> App<'static, 'static> {
+  cli("wip", "Mark a change as work in progress.")
+}
+
+pub fn cli_ready() -> App<'static, 'static> {
+  cli("ready", "Mark a change as ready for review.")
+}
+
+fn cli(name: &'static str, about: &'static str) -> App<'static, 'static> {
+  SubCommand::with_name(name)
+    .about(about)
+    .template("{about}\n\nUSAGE:\n    {usage}\n\n{all-args}")
+    .setting(clap::AppSettings::DeriveDisplayOrder)
+    .arg(Arg::with_name("change-id").required(true).help(
+      "Change identifier. \
+             Can be either a legacy numerical id (e.g. 15813), \
+             full or abbreviated Change-Id (e.g. Ic0ff33) \
+             or commit SHA-1 (e.g. d81b32ef).",
+    ))
+    .arg(
+      Arg::with_name("message")
+        .long("message")
+        .short("m")
+        .takes_value(true)
+        .help("Message to be added as review comment to the change."),
+    )
+    .arg(
+      Arg::with_name("remote")
+        .long("remote")
+        .short("r")
+        .takes_value(true)
+        .help("Specify an alternative remote to use."),
+    )
+}
+
+/// Execute the command, setting the change as work in progress if `wip`, or ready for review otherwise
+pub fn exec(config: &mut CliConfig, args: Option<&ArgMatches>, wip: bool) -> Result<(), failure::Error> {
+  let args = args.unwrap();
+  let remote = args.value_of("remote");
+  let change_id = args.value_of("change-id").unwrap();
+  let message = args.value_of("message");
+
+  let mut rest = get_remote_restapi_handler(config, remote)?;
+  let wip_input = WorkInProgressInput {
+    message: message.map(|m| m.into()),
+  };
+  if wip {
+    rest.set_work_in_progress(change_id, &wip_input)?;
+  } else {
+    rest.set_ready_for_review(change_id, &wip_input)?;
+  }
+
+  let change: ChangeInfo = rest.get_change(change_id, None)?;
+  if config.format != OutputFormat::Text {
+    let record = Record::new()
+      .field("number", change.number)
+      .field("work_in_progress", change.work_in_progress);
+    return output::write_record(config, &record);
+  }
+  let state = match change.work_in_progress {
+    true => "work in progress",
+    false => "ready for review",
+  };
+  writeln!(config.stdout, "Change {} is {}", change.number, state)?;
+
+  Ok(())
+}
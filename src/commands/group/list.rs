@@ -0,0 +1,123 @@
+use crate::config::{CliConfig, OutputFormat, Verbosity};
+use crate::handler::get_remote_restapi_handler;
+use crate::output::{self, Record};
+use crate::util;
+use clap::{App, Arg, ArgMatches, SubCommand};
+use gerlib::groups::{GroupEndpoints, GroupInfo, ListGroupsParams};
+use std::collections::BTreeMap;
+use std::io::Write;
+
+pub fn cli() -> App<'static, 'static> {
+  SubCommand::with_name("list")
+    .about("List groups visible to the caller.")
+    .template("{about}\n\nUSAGE:\n    {usage}\n\n{all-args}")
+    .setting(clap::AppSettings::DeriveDisplayOrder)
+    .arg(
+      Arg::with_name("owned")
+        .long("owned")
+        .help("Only list groups owned by the caller."),
+    )
+    .arg(
+      Arg::with_name("user")
+        .long("user")
+        .short("u")
+        .takes_value(true)
+        .value_name("ACCOUNT")
+        .help("Only list groups that contain the given user."),
+    )
+    .arg(
+      Arg::with_name("match")
+        .long("match")
+        .short("m")
+        .takes_value(true)
+        .value_name("STRING")
+        .help("Limit the results to those groups that match the specified substring (case insensitive)."),
+    )
+    .arg(
+      Arg::with_name("limit")
+        .long("limit")
+        .short("n")
+        .takes_value(true)
+        .value_name("max-count")
+        .validator(util::validate::is_u32)
+        .help("Limit the number of groups to output."),
+    )
+    .arg(
+      Arg::with_name("start")
+        .long("start")
+        .short("S")
+        .takes_value(true)
+        .value_name("offset")
+        .validator(util::validate::is_u32)
+        .help("Skip the given number of groups from the beginning of the results."),
+    )
+    .arg(
+      Arg::with_name("remote")
+        .long("remote")
+        .short("r")
+        .takes_value(true)
+        .value_name("NAME")
+        .help("Specify an alternative remote to use."),
+    )
+}
+
+pub fn exec(config: &mut CliConfig, args: Option<&ArgMatches>) -> Result<(), failure::Error> {
+  let args = args.unwrap();
+  let verbose: Verbosity = args.occurrences_of("verbose").into();
+  let remote = args.value_of("remote");
+
+  let mut rest = get_remote_restapi_handler(config, remote)?;
+  let params = ListGroupsParams {
+    owned: args.is_present("owned"),
+    user: args.value_of("user").map(|u| u.into()),
+    substring: args.value_of("match").map(|m| m.into()),
+    limit: args.value_of("limit").map(|n| n.parse::<u32>().unwrap()),
+    start: args.value_of("start").map(|n| n.parse::<u32>().unwrap()),
+  };
+  let groups: BTreeMap<String, GroupInfo> = rest.list_groups(&params)?;
+
+  if config.format != OutputFormat::Text {
+    let records: Vec<Record> = groups.iter().map(|(name, group)| record(name, group)).collect();
+    return output::write_records(config, &records);
+  }
+
+  if groups.is_empty() {
+    writeln!(config.stdout, "No groups.")?;
+    return Ok(());
+  }
+
+  let name_maxlen = groups.keys().map(|name| name.len()).max().unwrap_or(0);
+  let mut stdout = config.stdout.lock();
+  for (name, group) in &groups {
+    match (&group.description, verbose >= Verbosity::Verbose) {
+      (Some(description), true) => {
+        let padding = name_maxlen - name.len();
+        writeln!(
+          stdout,
+          "{}{2:1$} - {3}",
+          name,
+          padding,
+          "",
+          description.replace('\n', " ")
+        )?;
+      }
+      _ => writeln!(stdout, "{}", name)?,
+    }
+  }
+
+  Ok(())
+}
+
+/// Build the output record of a group
+pub fn record(name: &str, group: &GroupInfo) -> Record {
+  Record::new()
+    .field("name", name)
+    .field("id", &group.id)
+    .field("group_id", group.group_id)
+    .field("description", &group.description)
+    .field("owner", &group.owner)
+    .field(
+      "visible_to_all",
+      group.options.as_ref().and_then(|o| o.visible_to_all).unwrap_or(false),
+    )
+}
@@ -0,0 +1,71 @@
+use crate::config::{CliConfig, OutputFormat};
+use crate::handler::get_remote_restapi_handler;
+use crate::output::{self, Record};
+use clap::{App, Arg, ArgMatches, SubCommand};
+use gerlib::groups::GroupEndpoints;
+use std::io::Write;
+
+pub fn cli_add() -> App<'static, 'static> {
+  cli("add-member", "Add members to a group.")
+}
+
+pub fn cli_remove() -> App<'static, 'static> {
+  cli("remove-member", "Remove members from a group.")
+}
+
+fn cli(name: &'static str, about: &'static str) -> App<'static, 'static> {
+  SubCommand::with_name(name)
+    .about(about)
+    .template("{about}\n\nUSAGE:\n    {usage}\n\n{all-args}")
+    .setting(clap::AppSettings::DeriveDisplayOrder)
+    .arg(
+      Arg::with_name("group")
+        .required(true)
+        .help("Group name, UUID or numeric id."),
+    )
+    .arg(
+      Arg::with_name("account")
+        .required(true)
+        .multiple(true)
+        .help("Account identifier: numeric id, email or username."),
+    )
+    .arg(
+      Arg::with_name("remote")
+        .long("remote")
+        .short("r")
+        .takes_value(true)
+        .value_name("NAME")
+        .help("Specify an alternative remote to use."),
+    )
+}
+
+/// Execute the command, adding the accounts to the group if `add`, or removing them otherwise
+pub fn exec(config: &mut CliConfig, args: Option<&ArgMatches>, add: bool) -> Result<(), failure::Error> {
+  let args = args.unwrap();
+  let remote = args.value_of("remote");
+  let group = args.value_of("group").unwrap();
+
+  let mut rest = get_remote_restapi_handler(config, remote)?;
+  let mut records = Vec::new();
+  for account in args.values_of("account").unwrap() {
+    if add {
+      rest.add_group_member(group, account)?;
+    } else {
+      rest.remove_group_member(group, account)?;
+    }
+    if config.format == OutputFormat::Text {
+      let action = if add { "Added" } else { "Removed" };
+      let preposition = if add { "to" } else { "from" };
+      writeln!(config.stdout, "{} {} {} group {}", action, account, preposition, group)?;
+    } else {
+      records.push(
+        Record::new()
+          .field("group", group)
+          .field("account", account)
+          .field("member", add),
+      );
+    }
+  }
+
+  output::write_records(config, &records)
+}
@@ -0,0 +1,37 @@
+use crate::config::CliConfig;
+use clap::{App, ArgMatches, SubCommand};
+use gerlib::groups::GroupInfo;
+
+mod list;
+mod members;
+mod show;
+
+/// Build the CLI
+pub fn cli() -> App<'static, 'static> {
+  SubCommand::with_name("group")
+    .about("Lists groups and manages group membership.")
+    .template("{about}\n\nUSAGE:\n    {usage}\n\n{all-args}")
+    .subcommands(vec![
+      list::cli(),
+      show::cli(),
+      members::cli_add(),
+      members::cli_remove(),
+    ])
+}
+
+/// Execute the group command
+pub fn exec(config: &mut CliConfig, args: Option<&ArgMatches>) -> Result<(), failure::Error> {
+  let args = args.unwrap();
+  match args.subcommand() {
+    ("list", subargs) => list::exec(config, subargs),
+    ("show", subargs) => show::exec(config, subargs),
+    ("add-member", subargs) => members::exec(config, subargs, true),
+    ("remove-member", subargs) => members::exec(config, subargs, false),
+    _ => Ok(()),
+  }
+}
+
+/// Name of a group to be displayed, falling back to its id
+fn group_name(group: &GroupInfo) -> &str {
+  group.name.as_deref().unwrap_or(&group.id)
+}
@@ -0,0 +1,78 @@
+use super::{group_name, list};
+use crate::config::{CliConfig, OutputFormat};
+use crate::handler::get_remote_restapi_handler;
+use crate::output;
+use crate::util;
+use clap::{App, Arg, ArgMatches, SubCommand};
+use gerlib::accounts::AccountInfo;
+use gerlib::groups::{GroupEndpoints, GroupInfo};
+use std::io::Write;
+use termcolor::{ColorSpec, WriteColor};
+
+pub fn cli() -> App<'static, 'static> {
+  SubCommand::with_name("show")
+    .about("Show owner, members and included groups of a group.")
+    .template("{about}\n\nUSAGE:\n    {usage}\n\n{all-args}")
+    .setting(clap::AppSettings::DeriveDisplayOrder)
+    .arg(
+      Arg::with_name("group")
+        .required(true)
+        .help("Group name, UUID or numeric id."),
+    )
+    .arg(
+      Arg::with_name("remote")
+        .long("remote")
+        .short("r")
+        .takes_value(true)
+        .value_name("NAME")
+        .help("Specify an alternative remote to use."),
+    )
+}
+
+pub fn exec(config: &mut CliConfig, args: Option<&ArgMatches>) -> Result<(), failure::Error> {
+  let args = args.unwrap();
+  let remote = args.value_of("remote");
+  let group_id = args.value_of("group").unwrap();
+
+  let mut rest = get_remote_restapi_handler(config, remote)?;
+  let group: GroupInfo = rest.get_group_detail(group_id)?;
+  let no_members = Vec::new();
+  let members: &Vec<AccountInfo> = group.members.as_ref().unwrap_or(&no_members);
+  let no_includes = Vec::new();
+  let includes: &Vec<GroupInfo> = group.includes.as_ref().unwrap_or(&no_includes);
+
+  if config.format != OutputFormat::Text {
+    let member_names: Vec<String> = members.iter().map(util::account_name_email).collect();
+    let include_names: Vec<&str> = includes.iter().map(group_name).collect();
+    let record = list::record(group_name(&group), &group)
+      .field("members", member_names)
+      .field("includes", include_names);
+    return output::write_record(config, &record);
+  }
+
+  let mut stdout = config.stdout.lock();
+  stdout.set_color(ColorSpec::new().set_bold(true))?;
+  writeln!(stdout, "{}", group_name(&group))?;
+  stdout.reset()?;
+  writeln!(stdout, "{:12} {}", "Id:", group.id)?;
+  if let Some(owner) = &group.owner {
+    writeln!(stdout, "{:12} {}", "Owner:", owner)?;
+  }
+  if let Some(description) = &group.description {
+    writeln!(stdout, "{:12} {}", "Description:", description)?;
+  }
+  writeln!(stdout)?;
+  writeln!(stdout, "Members ({}):", members.len())?;
+  for member in members {
+    writeln!(stdout, "  {}", util::account_name_email(member))?;
+  }
+  if !includes.is_empty() {
+    writeln!(stdout)?;
+    writeln!(stdout, "Included groups ({}):", includes.len())?;
+    for include in includes {
+      writeln!(stdout, "  {}", group_name(include))?;
+    }
+  }
+
+  Ok(())
+}
@@ -3,17 +3,31 @@ use clap::{App, ArgMatches};
 use crate::config::CliConfig;
 use crate::ui;
 
+pub mod account;
 pub mod change;
+pub mod group;
 pub mod project;
+pub mod push;
 pub mod remote;
 
 pub fn builtin() -> Vec<App<'static, 'static>> {
-  vec![change::cli(), project::cli(), remote::cli(), ui::cli()]
+  vec![
+    change::cli(),
+    account::cli(),
+    group::cli(),
+    push::cli(),
+    project::cli(),
+    remote::cli(),
+    ui::cli(),
+  ]
 }
 
 pub fn builtin_exec(cmd: &str) -> Option<fn(&mut CliConfig, Option<&ArgMatches>) -> Result<(), failure::Error>> {
   let func = match cmd {
     "change" => change::exec,
+    "account" => account::exec,
+    "group" => group::exec,
+    "push" => push::exec,
     "project" => project::exec,
     "remote" => remote::exec,
     "ui" => ui::exec,
@@ -0,0 +1,168 @@
+use crate::config::{CliConfig, OutputFormat, Verbosity};
+use crate::handler::get_remote_restapi_handler;
+use crate::output::{self, Record};
+use crate::util;
+use clap::{App, Arg, ArgMatches, SubCommand};
+use gerlib::projects::{ListBranchesParams, ListTagsParams, ProjectEndpoints};
+use std::io::Write;
+use termcolor::{Color, ColorSpec, WriteColor};
+
+pub fn cli_branches() -> App<'static, 'static> {
+  cli("branches", "List branches of a project.")
+}
+
+pub fn cli_tags() -> App<'static, 'static> {
+  cli("tags", "List tags of a project.")
+}
+
+fn cli(name: &'static str, about: &'static str) -> App<'static, 'static> {
+  SubCommand::with_name(name)
+    .about(about)
+    .template("{about}\n\nUSAGE:\n    {usage}\n\n{all-args}")
+    .setting(clap::AppSettings::DeriveDisplayOrder)
+    .arg(Arg::with_name("project").required(true).help("Project name."))
+    .arg(
+      Arg::with_name("match")
+        .long("match")
+        .short("m")
+        .takes_value(true)
+        .value_name("STRING")
+        .conflicts_with("regex")
+        .help("Limit the results to those refs that match the specified substring (case insensitive)."),
+    )
+    .arg(
+      Arg::with_name("regex")
+        .long("regex")
+        .takes_value(true)
+        .value_name("REGEX")
+        .conflicts_with("match")
+        .help("Limit the results to those refs that match the specified regex."),
+    )
+    .arg(
+      Arg::with_name("limit")
+        .long("limit")
+        .short("n")
+        .takes_value(true)
+        .value_name("max-count")
+        .validator(util::validate::is_u32)
+        .help("Limit the number of refs to output."),
+    )
+    .arg(
+      Arg::with_name("start")
+        .long("start")
+        .short("S")
+        .takes_value(true)
+        .value_name("offset")
+        .validator(util::validate::is_u32)
+        .help("Skip the given number of refs from the beginning of the results."),
+    )
+    .arg(
+      Arg::with_name("remote")
+        .long("remote")
+        .short("r")
+        .takes_value(true)
+        .value_name("NAME")
+        .help("Specify an alternative remote to use."),
+    )
+}
+
+/// Execute the command, listing tags of the project if `tags`, or branches otherwise
+pub fn exec(config: &mut CliConfig, args: Option<&ArgMatches>, tags: bool) -> Result<(), failure::Error> {
+  let args = args.unwrap();
+  let verbose: Verbosity = args.occurrences_of("verbose").into();
+  let remote = args.value_of("remote");
+  let project = args.value_of("project").unwrap();
+  let limit = args.value_of("limit").map(|n| n.parse::<u32>().unwrap());
+  let skip = args.value_of("start").map(|n| n.parse::<u32>().unwrap());
+  let substring = args.value_of("match").map(|m| m.into());
+  let regex = args.value_of("regex").map(|r| r.into());
+
+  let mut rest = get_remote_restapi_handler(config, remote)?;
+
+  let records: Vec<Record> = if tags {
+    let params = ListTagsParams {
+      limit,
+      skip,
+      substring,
+      regex,
+    };
+    rest
+      .list_tags(project, &params)?
+      .iter()
+      .map(|tag| {
+        Record::new()
+          .field("name", tag.ref_.trim_start_matches("refs/tags/"))
+          .field("ref", &tag.ref_)
+          .field("revision", &tag.revision)
+          .field("object", &tag.object)
+          .field("message", &tag.message)
+          .field(
+            "tagger",
+            tag.tagger.as_ref().map(|t| format!("{} <{}>", t.name, t.email)),
+          )
+          .field("can_delete", tag.can_delete.unwrap_or(false))
+      })
+      .collect()
+  } else {
+    let params = ListBranchesParams {
+      limit,
+      skip,
+      substring,
+      regex,
+    };
+    rest
+      .list_branches(project, &params)?
+      .iter()
+      .map(|branch| {
+        Record::new()
+          .field("name", branch.ref_.trim_start_matches("refs/heads/"))
+          .field("ref", &branch.ref_)
+          .field("revision", &branch.revision)
+          .field("can_delete", branch.can_delete.unwrap_or(false))
+      })
+      .collect()
+  };
+
+  if config.format != OutputFormat::Text {
+    return output::write_records(config, &records);
+  }
+
+  if records.is_empty() {
+    writeln!(config.stdout, "No {}.", if tags { "tags" } else { "branches" })?;
+    return Ok(());
+  }
+
+  let field = |record: &Record, name: &str| record.get(name).map(output::value_to_string).unwrap_or_default();
+  let name_maxlen = records.iter().map(|r| field(r, "name").len()).max().unwrap_or(0);
+
+  let mut stdout = config.stdout.lock();
+  for record in &records {
+    let name = field(record, "name");
+    // annotated tags point to a tag object, show the commit it refers to instead
+    let object = field(record, "object");
+    let mut revision = if object.is_empty() {
+      field(record, "revision")
+    } else {
+      object
+    };
+    if verbose == Verbosity::Normal && is_sha1(&revision) {
+      revision.truncate(7);
+    }
+    write!(stdout, "{0:1$} ", name, name_maxlen)?;
+    stdout.set_color(ColorSpec::new().set_fg(Some(Color::Yellow)))?;
+    write!(stdout, "{}", revision)?;
+    stdout.reset()?;
+    let message = field(record, "message");
+    if verbose >= Verbosity::Verbose && !message.is_empty() {
+      writeln!(stdout, " {}", message.lines().next().unwrap_or(""))?;
+    } else {
+      stdout.write_all(b"\n")?;
+    }
+  }
+
+  Ok(())
+}
+
+fn is_sha1(revision: &str) -> bool {
+  revision.len() == 40 && revision.chars().all(|c| c.is_ascii_hexdigit())
+}
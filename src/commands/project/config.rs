@@ -0,0 +1,224 @@
+use super::{parse_state, parse_submit_type, state_str, submit_type_str, STATE_VALUES, SUBMIT_TYPE_VALUES};
+use crate::config::{CliConfig, OutputFormat};
+use crate::handler::get_remote_restapi_handler;
+use crate::output::{self, Record};
+use crate::util;
+use clap::{App, Arg, ArgMatches, SubCommand};
+use gerlib::projects::{ConfigInfo, ConfigInput, ProjectEndpoints, ProjectParentInput};
+use gerlib::GerritRestApi;
+use std::io::Write;
+use termcolor::{Color, ColorSpec, WriteColor};
+
+pub fn cli() -> App<'static, 'static> {
+  SubCommand::with_name("config")
+    .about("Show or edit the configuration of a project.")
+    .template("{about}\n\nUSAGE:\n    {usage}\n\n{all-args}")
+    .setting(clap::AppSettings::DeriveDisplayOrder)
+    .arg(Arg::with_name("name").required(true).help("Project name."))
+    .arg(
+      Arg::with_name("description")
+        .long("description")
+        .short("d")
+        .takes_value(true)
+        .help("Set the description of the project."),
+    )
+    .arg(
+      Arg::with_name("submit-type")
+        .long("submit-type")
+        .takes_value(true)
+        .possible_values(SUBMIT_TYPE_VALUES)
+        .help("Set the submit type of the project."),
+    )
+    .arg(
+      Arg::with_name("state")
+        .long("state")
+        .takes_value(true)
+        .possible_values(STATE_VALUES)
+        .help("Set the state of the project."),
+    )
+    .arg(
+      Arg::with_name("parent")
+        .long("parent")
+        .takes_value(true)
+        .value_name("PROJECT")
+        .help("Set the parent project to inherit access rights from."),
+    )
+    .arg(
+      Arg::with_name("yes")
+        .long("yes")
+        .short("y")
+        .help("Apply changes without asking for confirmation."),
+    )
+    .arg(
+      Arg::with_name("remote")
+        .long("remote")
+        .short("r")
+        .takes_value(true)
+        .value_name("NAME")
+        .help("Specify an alternative remote to use."),
+    )
+}
+
+/// A configuration setting of the project, with the value to change it to, if any
+struct Setting {
+  name: &'static str,
+  current: String,
+  new: Option<String>,
+}
+
+impl Setting {
+  fn changed(&self) -> bool {
+    self.new.as_ref().map_or(false, |new| *new != self.current)
+  }
+}
+
+pub fn exec(config: &mut CliConfig, args: Option<&ArgMatches>) -> Result<(), failure::Error> {
+  let args = args.unwrap();
+  let remote = args.value_of("remote");
+  let name = args.value_of("name").unwrap();
+  let yes = args.is_present("yes");
+
+  let mut rest = get_remote_restapi_handler(config, remote)?;
+  let settings = get_settings(&mut rest, name, args)?;
+
+  if settings.iter().all(|s| s.new.is_none()) {
+    return show_settings(config, name, &settings);
+  }
+
+  let changes: Vec<&Setting> = settings.iter().filter(|s| s.changed()).collect();
+  if changes.is_empty() {
+    if config.format == OutputFormat::Text {
+      writeln!(config.stdout, "Nothing to change.")?;
+      return Ok(());
+    }
+    return show_settings(config, name, &settings);
+  }
+
+  if !yes {
+    if config.format != OutputFormat::Text {
+      return Err(failure::err_msg(
+        "use --yes to apply changes with machine-readable output",
+      ));
+    }
+    show_diff(config, name, &changes)?;
+    if !util::prompt_confirm(&mut config.stdout, "Apply these changes?")? {
+      writeln!(config.stdout, "Aborted.")?;
+      return Ok(());
+    }
+  }
+
+  let changed = |setting: &str| changes.iter().any(|s| s.name == setting);
+  if changed("description") || changed("submit_type") || changed("state") {
+    let config_input = ConfigInput {
+      description: args.value_of("description").map(|d| d.into()),
+      use_contributor_agreements: None,
+      use_content_merge: None,
+      use_signed_off_by: None,
+      create_new_change_for_all_not_in_target: None,
+      require_change_id: None,
+      enable_signed_push: None,
+      require_signed_push: None,
+      reject_implicit_merges: None,
+      private_by_default: None,
+      work_in_progress_by_default: None,
+      max_object_size_limit: None,
+      submit_type: args.value_of("submit-type").map(parse_submit_type),
+      state: args.value_of("state").map(parse_state),
+      plugin_config_values: None,
+      reject_empty_commit: None,
+    };
+    rest.set_config(name, &config_input)?;
+  }
+  if changed("parent") {
+    let parent_input = ProjectParentInput {
+      parent: args.value_of("parent").unwrap().into(),
+      commit_message: None,
+    };
+    rest.set_parent(name, &parent_input)?;
+  }
+
+  let settings = get_settings(&mut rest, name, args)?;
+  show_settings(config, name, &settings)
+}
+
+/// Get the current settings of the project, along with the new values given as arguments
+fn get_settings(rest: &mut GerritRestApi, name: &str, args: &ArgMatches) -> Result<Vec<Setting>, failure::Error> {
+  let project_config: ConfigInfo = rest.get_config(name)?;
+  let parent: String = rest.get_parent(name)?;
+  let new = |arg: &str| args.value_of(arg).map(|v| v.to_owned());
+  Ok(vec![
+    Setting {
+      name: "parent",
+      current: parent,
+      new: new("parent"),
+    },
+    Setting {
+      name: "description",
+      current: project_config.description.unwrap_or_default(),
+      new: new("description"),
+    },
+    Setting {
+      name: "submit_type",
+      current: project_config
+        .submit_type
+        .as_ref()
+        .map(submit_type_str)
+        .unwrap_or("inherit")
+        .into(),
+      new: new("submit-type"),
+    },
+    Setting {
+      name: "state",
+      current: project_config.state.as_ref().map(state_str).unwrap_or("active").into(),
+      new: new("state"),
+    },
+  ])
+}
+
+fn show_settings(config: &CliConfig, name: &str, settings: &[Setting]) -> Result<(), failure::Error> {
+  if config.format != OutputFormat::Text {
+    let mut record = Record::new().field("name", name);
+    for setting in settings {
+      record = record.field(setting.name, &setting.current);
+    }
+    return output::write_record(config, &record);
+  }
+
+  let mut stdout = config.stdout.lock();
+  stdout.set_color(ColorSpec::new().set_bold(true))?;
+  writeln!(stdout, "Project {}", name)?;
+  stdout.reset()?;
+  for setting in settings {
+    let mut lines = setting.current.lines();
+    writeln!(
+      stdout,
+      "  {:12} {}",
+      format!("{}:", setting.name),
+      lines.next().unwrap_or("")
+    )?;
+    for line in lines {
+      writeln!(stdout, "  {:12} {}", "", line)?;
+    }
+  }
+  Ok(())
+}
+
+/// Show a preview of the changes to be applied to the project's configuration
+fn show_diff(config: &CliConfig, name: &str, changes: &[&Setting]) -> Result<(), failure::Error> {
+  let mut stdout = config.stdout.lock();
+  stdout.set_color(ColorSpec::new().set_bold(true))?;
+  writeln!(stdout, "Changes to project {}:", name)?;
+  stdout.reset()?;
+  for setting in changes {
+    stdout.set_color(ColorSpec::new().set_fg(Some(Color::Red)))?;
+    for line in setting.current.lines() {
+      writeln!(stdout, "- {}: {}", setting.name, line)?;
+    }
+    stdout.set_color(ColorSpec::new().set_fg(Some(Color::Green)))?;
+    for line in setting.new.as_ref().unwrap().lines() {
+      writeln!(stdout, "+ {}: {}", setting.name, line)?;
+    }
+    stdout.reset()?;
+  }
+  Ok(())
+}
@@ -0,0 +1,112 @@
+use super::list;
+use super::{parse_submit_type, SUBMIT_TYPE_VALUES};
+use crate::config::{CliConfig, OutputFormat};
+use crate::handler::get_remote_restapi_handler;
+use crate::output;
+use clap::{App, Arg, ArgMatches, SubCommand};
+use gerlib::projects::{ProjectEndpoints, ProjectInfo, ProjectInput};
+use std::io::Write;
+
+pub fn cli() -> App<'static, 'static> {
+  SubCommand::with_name("create")
+    .about("Create a new project.")
+    .template("{about}\n\nUSAGE:\n    {usage}\n\n{all-args}")
+    .setting(clap::AppSettings::DeriveDisplayOrder)
+    .arg(
+      Arg::with_name("name")
+        .required(true)
+        .help("Name of the project to create."),
+    )
+    .arg(
+      Arg::with_name("parent")
+        .long("parent")
+        .takes_value(true)
+        .value_name("PROJECT")
+        .help("Project to inherit access rights from. Defaults to All-Projects."),
+    )
+    .arg(
+      Arg::with_name("description")
+        .long("description")
+        .short("d")
+        .takes_value(true)
+        .help("Description of the project."),
+    )
+    .arg(
+      Arg::with_name("branch")
+        .long("branch")
+        .short("b")
+        .takes_value(true)
+        .multiple(true)
+        .number_of_values(1)
+        .help("Branch to create, the first one becomes HEAD. Can be specified multiple times."),
+    )
+    .arg(
+      Arg::with_name("empty-commit")
+        .long("empty-commit")
+        .help("Create an initial empty commit in the project."),
+    )
+    .arg(
+      Arg::with_name("submit-type")
+        .long("submit-type")
+        .takes_value(true)
+        .possible_values(SUBMIT_TYPE_VALUES)
+        .help("Submit type of the project."),
+    )
+    .arg(
+      Arg::with_name("owner")
+        .long("owner")
+        .takes_value(true)
+        .multiple(true)
+        .number_of_values(1)
+        .value_name("GROUP")
+        .help("Group that becomes owner of the project. Can be specified multiple times."),
+    )
+    .arg(
+      Arg::with_name("permissions-only")
+        .long("permissions-only")
+        .help("Create a project that is only used to inherit access rights from."),
+    )
+    .arg(
+      Arg::with_name("remote")
+        .long("remote")
+        .short("r")
+        .takes_value(true)
+        .value_name("NAME")
+        .help("Specify an alternative remote to use."),
+    )
+}
+
+pub fn exec(config: &mut CliConfig, args: Option<&ArgMatches>) -> Result<(), failure::Error> {
+  let args = args.unwrap();
+  let remote = args.value_of("remote");
+  let name = args.value_of("name").unwrap();
+
+  let mut rest = get_remote_restapi_handler(config, remote)?;
+  let project_input = ProjectInput {
+    name: Some(name.into()),
+    parent: args.value_of("parent").map(|p| p.into()),
+    description: args.value_of("description").map(|d| d.into()),
+    permissions_only: Some(args.is_present("permissions-only")),
+    create_empty_commit: Some(args.is_present("empty-commit")),
+    submit_type: args.value_of("submit-type").map(parse_submit_type),
+    branches: args.values_of("branch").map(|v| v.map(|b| b.into()).collect()),
+    owners: args.values_of("owner").map(|v| v.map(|o| o.into()).collect()),
+    use_contributor_agreements: None,
+    use_signed_off_by: None,
+    create_new_change_for_all_not_in_target: None,
+    use_content_merge: None,
+    require_change_id: None,
+    enable_signed_push: None,
+    require_signed_push: None,
+    max_object_size_limit: None,
+    plugin_config_values: None,
+    reject_empty_commit: None,
+  };
+  let project: ProjectInfo = rest.create_project(name, &project_input)?;
+
+  if config.format != OutputFormat::Text {
+    return output::write_record(config, &list::record(name, &project));
+  }
+  writeln!(config.stdout, "Created project {}", name)?;
+  Ok(())
+}
@@ -0,0 +1,59 @@
+use crate::config::{CliConfig, OutputFormat};
+use crate::handler::get_remote_restapi_handler;
+use crate::output::{self, Record};
+use clap::{App, Arg, ArgMatches, SubCommand};
+use gerlib::projects::{BranchInfo, BranchInput, ProjectEndpoints};
+use std::io::Write;
+
+pub fn cli() -> App<'static, 'static> {
+  SubCommand::with_name("create-branch")
+    .about("Create a new branch in a project.")
+    .template("{about}\n\nUSAGE:\n    {usage}\n\n{all-args}")
+    .setting(clap::AppSettings::DeriveDisplayOrder)
+    .arg(Arg::with_name("project").required(true).help("Project name."))
+    .arg(
+      Arg::with_name("name")
+        .required(true)
+        .help("Name of the branch to create."),
+    )
+    .arg(
+      Arg::with_name("revision")
+        .long("revision")
+        .takes_value(true)
+        .value_name("SHA1|REF")
+        .help("Base revision of the new branch. Defaults to HEAD of the project."),
+    )
+    .arg(
+      Arg::with_name("remote")
+        .long("remote")
+        .short("r")
+        .takes_value(true)
+        .value_name("NAME")
+        .help("Specify an alternative remote to use."),
+    )
+}
+
+pub fn exec(config: &mut CliConfig, args: Option<&ArgMatches>) -> Result<(), failure::Error> {
+  let args = args.unwrap();
+  let remote = args.value_of("remote");
+  let project = args.value_of("project").unwrap();
+  let name = args.value_of("name").unwrap();
+  let revision = args.value_of("revision");
+
+  let mut rest = get_remote_restapi_handler(config, remote)?;
+  let branch_input = BranchInput {
+    ref_: None,
+    revision: revision.map(|r| r.into()),
+  };
+  let branch: BranchInfo = rest.create_branch(project, name, &branch_input)?;
+
+  if config.format != OutputFormat::Text {
+    let record = Record::new()
+      .field("project", project)
+      .field("ref", &branch.ref_)
+      .field("revision", &branch.revision);
+    return output::write_record(config, &record);
+  }
+  writeln!(config.stdout, "Created branch {} at {}", branch.ref_, branch.revision)?;
+  Ok(())
+}
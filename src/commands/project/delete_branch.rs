@@ -0,0 +1,67 @@
+use crate::config::{CliConfig, OutputFormat};
+use crate::handler::get_remote_restapi_handler;
+use crate::output::{self, Record};
+use crate::util;
+use clap::{App, Arg, ArgMatches, SubCommand};
+use gerlib::projects::ProjectEndpoints;
+use std::io::Write;
+
+pub fn cli() -> App<'static, 'static> {
+  SubCommand::with_name("delete-branch")
+    .about("Delete a branch of a project.")
+    .template("{about}\n\nUSAGE:\n    {usage}\n\n{all-args}")
+    .setting(clap::AppSettings::DeriveDisplayOrder)
+    .arg(Arg::with_name("project").required(true).help("Project name."))
+    .arg(
+      Arg::with_name("name")
+        .required(true)
+        .help("Name of the branch to delete."),
+    )
+    .arg(
+      Arg::with_name("remote")
+        .long("remote")
+        .short("r")
+        .takes_value(true)
+        .value_name("NAME")
+        .help("Specify an alternative remote to use."),
+    )
+    .arg(
+      Arg::with_name("yes")
+        .long("yes")
+        .short("y")
+        .help("Delete the branch without asking for confirmation."),
+    )
+}
+
+pub fn exec(config: &mut CliConfig, args: Option<&ArgMatches>) -> Result<(), failure::Error> {
+  let args = args.unwrap();
+  let remote = args.value_of("remote");
+  let project = args.value_of("project").unwrap();
+  let name = args.value_of("name").unwrap();
+
+  if !args.is_present("yes") {
+    if config.format != OutputFormat::Text {
+      return Err(failure::err_msg(
+        "use --yes to delete a branch with machine-readable output",
+      ));
+    }
+    let question = format!("Delete branch {} of {}?", name, project);
+    if !util::prompt_confirm(&mut config.stdout, &question)? {
+      writeln!(config.stdout, "Aborted.")?;
+      return Ok(());
+    }
+  }
+
+  let mut rest = get_remote_restapi_handler(config, remote)?;
+  rest.delete_branch(project, name)?;
+
+  if config.format != OutputFormat::Text {
+    let record = Record::new()
+      .field("project", project)
+      .field("branch", name)
+      .field("deleted", true);
+    return output::write_record(config, &record);
+  }
+  writeln!(config.stdout, "Deleted branch {} of {}", name, project)?;
+  Ok(())
+}
@@ -0,0 +1,265 @@
+use super::{parse_state, state_str, STATE_VALUES};
+use crate::config::{CliConfig, OutputFormat, Verbosity};
+use crate::handler::get_remote_restapi_handler;
+use crate::output::{self, Record};
+use crate::util;
+use clap::{App, Arg, ArgMatches, SubCommand};
+use gerlib::projects::{ListProjectsParams, ProjectEndpoints, ProjectInfo, ProjectType};
+use std::collections::BTreeMap;
+use std::io::Write;
+use termcolor::{Color, ColorSpec, StandardStreamLock, WriteColor};
+
+pub fn cli() -> App<'static, 'static> {
+  SubCommand::with_name("list")
+    .about("List projects visible to the caller.")
+    .template("{about}\n\nUSAGE:\n    {usage}\n\n{all-args}")
+    .setting(clap::AppSettings::DeriveDisplayOrder)
+    .arg(
+      Arg::with_name("project")
+        .required(false)
+        .multiple(true)
+        .help("Project name."),
+    )
+    .arg(
+      Arg::with_name("limit")
+        .long("limit")
+        .short("n")
+        .takes_value(true)
+        .value_name("max-count")
+        .validator(util::validate::is_u32)
+        .help("Limit the number of projects to output."),
+    )
+    .arg(
+      Arg::with_name("start")
+        .long("start")
+        .short("S")
+        .takes_value(true)
+        .value_name("offset")
+        .validator(util::validate::is_u32)
+        .help("Skip the given number of projects from the beginning of the results."),
+    )
+    .arg(
+      Arg::with_name("remote")
+        .long("remote")
+        .short("r")
+        .takes_value(true)
+        .value_name("NAME")
+        .help("Specify an alternative remote to use."),
+    )
+    .arg(
+      Arg::with_name("prefix")
+        .long("prefix")
+        .short("p")
+        .takes_value(true)
+        .value_name("STRING")
+        .conflicts_with_all(&["match", "regex"])
+        .help(
+          "Limit the results to those projects that start with the specified prefix.\n\
+                   The match is case sensitive. May not be used together with m or r.",
+        ),
+    )
+    .arg(
+      Arg::with_name("match")
+        .long("match")
+        .short("m")
+        .takes_value(true)
+        .value_name("STRING")
+        .conflicts_with_all(&["prefix", "regex"])
+        .help(
+          "Limit the results to those projects that match the specified substring.\n\
+                   The match is case insensitive. May not be used together with p or r.",
+        ),
+    )
+    .arg(
+      Arg::with_name("regex")
+        .long("regex")
+        .takes_value(true)
+        .value_name("REGEX")
+        .conflicts_with_all(&["prefix", "match"])
+        .help(
+          "Limit the results to those projects that match the specified regex.\n\
+                   May not be used together with p or m.",
+        ),
+    )
+    .arg(
+      Arg::with_name("type")
+        .long("type")
+        .takes_value(true)
+        .value_name("TYPE")
+        .possible_values(&["code", "permissions", "all"])
+        .help("Limit the results to projects of the specified type."),
+    )
+    .arg(
+      Arg::with_name("state")
+        .long("state")
+        .takes_value(true)
+        .value_name("STATE")
+        .possible_values(STATE_VALUES)
+        .help("Limit the results to projects in the specified state."),
+    )
+    .arg(
+      Arg::with_name("parent")
+        .long("parent")
+        .conflicts_with("tree")
+        .help("Show the parent project of each project."),
+    )
+    .arg(
+      Arg::with_name("tree")
+        .long("tree")
+        .conflicts_with("parent")
+        .help("Show projects in a tree according to their parent projects."),
+    )
+}
+
+pub fn exec(config: &mut CliConfig, args: Option<&ArgMatches>) -> Result<(), failure::Error> {
+  let args = args.unwrap();
+  let verbose: Verbosity = args.occurrences_of("verbose").into();
+  let remote = args.value_of("remote");
+  let parent = args.is_present("parent");
+  let tree = args.is_present("tree");
+
+  let mut rest = get_remote_restapi_handler(config, remote)?;
+
+  let mut projects: BTreeMap<String, ProjectInfo> = BTreeMap::new();
+  if let Some(names) = args.values_of("project") {
+    for name in names {
+      projects.insert(name.to_owned(), rest.get_project(name)?);
+    }
+  } else {
+    let params = ListProjectsParams {
+      branch: None,
+      description: true,
+      limit: args.value_of("limit").map(|n| n.parse::<u32>().unwrap()),
+      prefix: args.value_of("prefix").map(|p| p.into()),
+      regex: args.value_of("regex").map(|r| r.into()),
+      skip: args.value_of("start").map(|n| n.parse::<u32>().unwrap()),
+      substring: args.value_of("match").map(|m| m.into()),
+      tree: parent || tree,
+      project_type: args.value_of("type").map(|t| match t {
+        "code" => ProjectType::Code,
+        "permissions" => ProjectType::Permissions,
+        _ => ProjectType::All,
+      }),
+      state: args.value_of("state").map(parse_state),
+      all: false,
+    };
+    projects.extend(rest.list_projects(&params)?);
+  }
+
+  if config.format != OutputFormat::Text {
+    let records: Vec<Record> = projects.iter().map(|(name, project)| record(name, project)).collect();
+    return output::write_records(config, &records);
+  }
+
+  if projects.is_empty() {
+    writeln!(config.stdout, "No projects.")?;
+    return Ok(());
+  }
+
+  if tree {
+    return show_tree(config, &projects, verbose);
+  }
+
+  let mut project_maxlen = 0;
+  for project in projects.keys() {
+    if project.len() > project_maxlen {
+      project_maxlen = project.len();
+    }
+  }
+
+  let mut stdout = config.stdout.lock();
+  for project in projects.iter() {
+    write!(stdout, "{}", project.0)?;
+    let mut padding = project_maxlen - project.0.len();
+    if parent {
+      stdout.set_color(ColorSpec::new().set_fg(Some(Color::Cyan)))?;
+      write!(
+        stdout,
+        "{0:1$} <- {2}",
+        "",
+        padding,
+        project.1.parent.as_deref().unwrap_or("-")
+      )?;
+      stdout.reset()?;
+      padding = 0;
+    }
+    write_description(&mut stdout, project.1, padding, project_maxlen, verbose)?;
+  }
+
+  Ok(())
+}
+
+/// Build the output record of a project
+pub fn record(name: &str, project: &ProjectInfo) -> Record {
+  Record::new()
+    .field("name", name)
+    .field("id", &project.id)
+    .field("parent", &project.parent)
+    .field("state", project.state.as_ref().map(|s| state_str(s)))
+    .field("description", &project.description)
+}
+
+/// Write the description of a project after its name, according to verbosity, and end the line.
+///
+/// `padding` aligns the description with other projects, `indent` aligns its subsequent lines.
+fn write_description(
+  stdout: &mut StandardStreamLock, project: &ProjectInfo, padding: usize, indent: usize, verbose: Verbosity,
+) -> Result<(), failure::Error> {
+  let no_description = "<no description>".to_string();
+  let description = project.description.as_ref().unwrap_or(&no_description);
+  if verbose == Verbosity::Verbose {
+    let desc = description.replace('\n', " ");
+    writeln!(stdout, "{0:1$} - {2}", "", padding, desc)?;
+  } else if verbose >= Verbosity::High {
+    let mut lines = description.lines();
+    writeln!(stdout, "{0:1$} - {2}", "", padding, lines.next().unwrap_or(""))?;
+    for line in lines {
+      writeln!(stdout, "{0:1$}   {2}", "", indent, line)?;
+    }
+  } else {
+    stdout.write_all(b"\n")?;
+  }
+  Ok(())
+}
+
+/// Show projects in a tree, children listed under their parent project
+fn show_tree(
+  config: &CliConfig, projects: &BTreeMap<String, ProjectInfo>, verbose: Verbosity,
+) -> Result<(), failure::Error> {
+  let mut children: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+  let mut roots = Vec::new();
+  for (name, project) in projects {
+    match project.parent.as_ref().filter(|p| projects.contains_key(p.as_str())) {
+      Some(parent) => children.entry(parent.as_str()).or_default().push(name.as_str()),
+      None => roots.push(name.as_str()),
+    }
+  }
+
+  let mut stdout = config.stdout.lock();
+  for root in roots {
+    show_tree_node(&mut stdout, projects, &children, root, "", "", verbose)?;
+  }
+  Ok(())
+}
+
+fn show_tree_node(
+  stdout: &mut StandardStreamLock, projects: &BTreeMap<String, ProjectInfo>, children: &BTreeMap<&str, Vec<&str>>,
+  name: &str, prefix: &str, child_prefix: &str, verbose: Verbosity,
+) -> Result<(), failure::Error> {
+  stdout.set_color(ColorSpec::new().set_fg(Some(Color::Cyan)))?;
+  write!(stdout, "{}", prefix)?;
+  stdout.reset()?;
+  write!(stdout, "{}", name)?;
+  let indent = child_prefix.chars().count() + name.chars().count();
+  write_description(stdout, &projects[name], 0, indent, verbose)?;
+
+  if let Some(names) = children.get(name) {
+    for (i, child) in names.iter().enumerate() {
+      let last = i + 1 == names.len();
+      let prefix = format!("{}{}", child_prefix, if last { "└── " } else { "├── " });
+      let next_prefix = format!("{}{}", child_prefix, if last { "    " } else { "│   " });
+      show_tree_node(stdout, projects, children, child, &prefix, &next_prefix, verbose)?;
+    }
+  }
+  Ok(())
+}
@@ -0,0 +1,94 @@
+use crate::config::CliConfig;
+use clap::{App, ArgMatches, SubCommand};
+use gerlib::projects::{ProjectState, SubmitType};
+
+mod branches;
+mod config;
+mod create;
+mod create_branch;
+mod delete_branch;
+mod list;
+
+/// Build the CLI
+pub fn cli() -> App<'static, 'static> {
+  SubCommand::with_name("project")
+    .about("Lists projects and information about projects.")
+    .template("{about}\n\nUSAGE:\n    {usage}\n\n{all-args}")
+    .subcommands(vec![
+      list::cli(),
+      create::cli(),
+      config::cli(),
+      branches::cli_branches(),
+      branches::cli_tags(),
+      create_branch::cli(),
+      delete_branch::cli(),
+    ])
+}
+
+/// Execute the project command, listing all projects when no subcommand is given
+pub fn exec(config: &mut CliConfig, args: Option<&ArgMatches>) -> Result<(), failure::Error> {
+  let args = args.unwrap();
+  match args.subcommand() {
+    ("list", subargs) => list::exec(config, subargs),
+    ("create", subargs) => create::exec(config, subargs),
+    ("config", subargs) => config::exec(config, subargs),
+    ("branches", subargs) => branches::exec(config, subargs, false),
+    ("tags", subargs) => branches::exec(config, subargs, true),
+    ("create-branch", subargs) => create_branch::exec(config, subargs),
+    ("delete-branch", subargs) => delete_branch::exec(config, subargs),
+    ("", _) => list::exec(config, Some(args)),
+    _ => Ok(()),
+  }
+}
+
+static STATE_VALUES: &[&str] = &["active", "read-only", "hidden"];
+
+static SUBMIT_TYPE_VALUES: &[&str] = &[
+  "inherit",
+  "merge-if-necessary",
+  "fast-forward-only",
+  "rebase-if-necessary",
+  "rebase-always",
+  "merge-always",
+  "cherry-pick",
+];
+
+fn parse_state(state: &str) -> ProjectState {
+  match state {
+    "read-only" => ProjectState::ReadOnly,
+    "hidden" => ProjectState::Hidden,
+    _ => ProjectState::Active,
+  }
+}
+
+fn state_str(state: &ProjectState) -> &'static str {
+  match state {
+    ProjectState::Active => "active",
+    ProjectState::ReadOnly => "read-only",
+    ProjectState::Hidden => "hidden",
+  }
+}
+
+fn parse_submit_type(submit_type: &str) -> SubmitType {
+  match submit_type {
+    "merge-if-necessary" => SubmitType::MergeIfNecessary,
+    "fast-forward-only" => SubmitType::FastForwardOnly,
+    "rebase-if-necessary" => SubmitType::RebaseIfNecessary,
+    "rebase-always" => SubmitType::RebaseAlways,
+    "merge-always" => SubmitType::MergeAlways,
+    "cherry-pick" => SubmitType::CherryPick,
+    _ => SubmitType::Inherit,
+  }
+}
+
+fn submit_type_str(submit_type: &SubmitType) -> &'static str {
+  match submit_type {
+    SubmitType::Inherit => "inherit",
+    SubmitType::MergeIfNecessary => "merge-if-necessary",
+    SubmitType::FastForwardOnly => "fast-forward-only",
+    SubmitType::RebaseIfNecessary => "rebase-if-necessary",
+    SubmitType::RebaseAlways => "rebase-always",
+    SubmitType::MergeAlways => "merge-always",
+    SubmitType::CherryPick => "cherry-pick",
+  }
+}
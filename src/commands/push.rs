@@ -0,0 +1,291 @@
+use crate::config::{CliConfig, OutputFormat};
+use crate::handler::get_remote_opts;
+use crate::output::{self, Record};
+use crate::util;
+use clap::{App, Arg, ArgMatches, SubCommand};
+use git2::{PushOptions, Repository};
+use std::io::Write;
+use termcolor::{Color, ColorSpec, WriteColor};
+
+pub fn cli() -> App<'static, 'static> {
+  SubCommand::with_name("push")
+    .about("Push local commits for review.")
+    .template("{about}\n\nUSAGE:\n    {usage}\n\n{all-args}\n\n{after-help}")
+    .after_help("EXAMPLE:\n    ger push -b master -t my-topic -R alice -R bob --wip")
+    .setting(clap::AppSettings::DeriveDisplayOrder)
+    .arg(
+      Arg::with_name("branch")
+        .long("branch")
+        .short("b")
+        .takes_value(true)
+        .help(
+          "Target branch of the review. \
+                     Defaults to the upstream branch of the current branch, or master.",
+        ),
+    )
+    .arg(
+      Arg::with_name("project")
+        .long("project")
+        .short("p")
+        .takes_value(true)
+        .help(
+          "Gerrit project to push to. Defaults to the project mapped to the repository \
+                     by 'ger remote import', or else the project of the 'origin' git remote.",
+        ),
+    )
+    .arg(
+      Arg::with_name("topic")
+        .long("topic")
+        .short("t")
+        .takes_value(true)
+        .help("Set the topic of the pushed changes."),
+    )
+    .arg(
+      Arg::with_name("reviewer")
+        .long("reviewer")
+        .short("R")
+        .takes_value(true)
+        .multiple(true)
+        .number_of_values(1)
+        .value_name("user")
+        .help("Add a reviewer to the pushed changes. Can be specified multiple times."),
+    )
+    .arg(
+      Arg::with_name("cc")
+        .long("cc")
+        .takes_value(true)
+        .multiple(true)
+        .number_of_values(1)
+        .value_name("user")
+        .help("Add a user to CC of the pushed changes. Can be specified multiple times."),
+    )
+    .arg(
+      Arg::with_name("hashtag")
+        .long("hashtag")
+        .takes_value(true)
+        .multiple(true)
+        .number_of_values(1)
+        .value_name("tag")
+        .help("Add a hashtag to the pushed changes. Can be specified multiple times."),
+    )
+    .arg(
+      Arg::with_name("wip")
+        .long("wip")
+        .conflicts_with("ready")
+        .help("Mark the pushed changes as work in progress."),
+    )
+    .arg(
+      Arg::with_name("ready")
+        .long("ready")
+        .conflicts_with("wip")
+        .help("Mark the pushed changes as ready for review."),
+    )
+    .arg(
+      Arg::with_name("private")
+        .long("private")
+        .help("Mark the pushed changes as private."),
+    )
+    .arg(
+      Arg::with_name("message")
+        .long("message")
+        .short("m")
+        .takes_value(true)
+        .help("Message to be added to the new patch sets."),
+    )
+    .arg(
+      Arg::with_name("remote")
+        .long("remote")
+        .short("r")
+        .takes_value(true)
+        .help("Specify an alternative remote to use."),
+    )
+}
+
+pub fn exec(config: &mut CliConfig, args: Option<&ArgMatches>) -> Result<(), failure::Error> {
+  let args = args.unwrap();
+
+  let repo = Repository::discover(std::env::current_dir()?)?;
+  let mapped_remote = util::git::repo_remote(&repo);
+  let remote = args.value_of("remote").or_else(|| mapped_remote.as_deref());
+  let branch = match args.value_of("branch") {
+    Some(b) => b.to_owned(),
+    None => upstream_branch(&repo).unwrap_or_else(|| "master".to_owned()),
+  };
+  let project = match args.value_of("project") {
+    Some(p) => p.to_owned(),
+    None => util::git::repo_project(&repo)
+      .ok_or_else(|| failure::err_msg("could not figure out the project, please specify --project"))?,
+  };
+
+  let mut options = Vec::new();
+  if let Some(topic) = args.value_of("topic") {
+    options.push(push_option("topic", topic)?);
+  }
+  for reviewer in args.values_of("reviewer").into_iter().flatten() {
+    options.push(push_option("r", reviewer)?);
+  }
+  for cc in args.values_of("cc").into_iter().flatten() {
+    options.push(push_option("cc", cc)?);
+  }
+  for hashtag in args.values_of("hashtag").into_iter().flatten() {
+    options.push(push_option("hashtag", hashtag)?);
+  }
+  if args.is_present("wip") {
+    options.push("wip".to_owned());
+  }
+  if args.is_present("ready") {
+    options.push("ready".to_owned());
+  }
+  if args.is_present("private") {
+    options.push("private".to_owned());
+  }
+  if let Some(message) = args.value_of("message") {
+    options.push(format!("m={}", encode_message(message)));
+  }
+
+  let mut refspec = format!("HEAD:refs/for/{}", branch);
+  if !options.is_empty() {
+    refspec = format!("{}%{}", refspec, options.join(","));
+  }
+
+  let remote_opts = get_remote_opts(config, remote)?.clone();
+  let url = format!("{}/{}", remote_opts.url.trim_end_matches('/'), project);
+
+  let mut messages = Vec::new();
+  let mut rejected = None;
+  {
+    let mut callbacks = util::git::remote_callbacks(&remote_opts);
+    callbacks.sideband_progress(|data| {
+      messages.extend_from_slice(data);
+      true
+    });
+    callbacks.push_update_reference(|_refname, status| {
+      rejected = status.map(|s| s.to_owned());
+      Ok(())
+    });
+    util::git::set_ca_bundle(&remote_opts)?;
+    let headers = util::git::remote_headers(&remote_opts)?;
+    let headers: Vec<&str> = headers.iter().map(|h| h.as_str()).collect();
+    let mut push_options = PushOptions::new();
+    push_options
+      .remote_callbacks(callbacks)
+      .proxy_options(util::git::proxy_options(&remote_opts))
+      .custom_headers(&headers);
+    repo
+      .remote_anonymous(&url)?
+      .push(&[refspec.as_str()], Some(&mut push_options))?;
+  }
+
+  if let Some(reason) = rejected {
+    return Err(failure::err_msg(format!("push to {} rejected: {}", branch, reason)));
+  }
+
+  let messages = String::from_utf8_lossy(&messages);
+  let urls = change_urls(&messages);
+  if config.format != OutputFormat::Text {
+    let records: Vec<Record> = urls
+      .iter()
+      .map(|(url, description)| Record::new().field("url", url).field("description", description.trim()))
+      .collect();
+    return output::write_records(config, &records);
+  }
+  let mut stdout = config.stdout.lock();
+  if urls.is_empty() {
+    writeln!(stdout, "{}", messages.trim())?;
+  }
+  for (url, rest) in urls {
+    stdout.set_color(ColorSpec::new().set_fg(Some(Color::Yellow)))?;
+    write!(stdout, "{}", url)?;
+    stdout.reset()?;
+    writeln!(stdout, "{}", rest)?;
+  }
+
+  Ok(())
+}
+
+/// Get the name of the branch the current branch is tracking on its remote
+fn upstream_branch(repo: &Repository) -> Option<String> {
+  let head = repo.head().ok()?;
+  let name = head.shorthand()?;
+  let merge = repo.config().ok()?.get_string(&format!("branch.{}.merge", name)).ok()?;
+  Some(merge.trim_start_matches("refs/heads/").to_owned())
+}
+
+/// Format a `name=value` push option. Gerrit takes these values as is,
+/// so values which would break the refspec syntax are rejected.
+fn push_option(name: &str, value: &str) -> Result<String, failure::Error> {
+  if value.is_empty() || value.contains(|c: char| c == ',' || c.is_whitespace()) {
+    return Err(failure::err_msg(format!(
+      "invalid {} value '{}': must not be empty or contain commas or whitespace",
+      name, value
+    )));
+  }
+  Ok(format!("{}={}", name, value))
+}
+
+/// Percent-encode the characters of a push option message which Gerrit would otherwise
+/// take as refspec syntax or decode as a space
+fn encode_message(message: &str) -> String {
+  let mut encoded = String::with_capacity(message.len());
+  for c in message.chars() {
+    match c {
+      ' ' | '%' | ',' | '_' | '+' => encoded.push_str(&format!("%{:02X}", c as u8)),
+      _ => encoded.push(c),
+    }
+  }
+  encoded
+}
+
+/// Extract the change URLs and their description from the server reply to a push
+fn change_urls(messages: &str) -> Vec<(&str, &str)> {
+  messages
+    .lines()
+    .map(|line| line.trim())
+    .filter(|line| line.starts_with("http://") || line.starts_with("https://"))
+    .map(|line| match line.find(' ') {
+      Some(index) => line.split_at(index),
+      None => (line, ""),
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  /// Expect user and topic values to be passed unchanged
+  fn push_option_unchanged() {
+    assert_eq!(
+      "r=jane.doe@example.com",
+      push_option("r", "jane.doe@example.com").unwrap()
+    );
+    assert_eq!(
+      "topic=feature/foo-bar_2",
+      push_option("topic", "feature/foo-bar_2").unwrap()
+    );
+    assert_eq!("hashtag=needs+review", push_option("hashtag", "needs+review").unwrap());
+  }
+
+  #[test]
+  /// Expect values that would break the refspec to be rejected
+  fn push_option_invalid() {
+    assert!(push_option("r", "alice,bob").is_err());
+    assert!(push_option("cc", "jane doe").is_err());
+    assert!(push_option("topic", "foo\tbar").is_err());
+    assert!(push_option("hashtag", "").is_err());
+  }
+
+  #[test]
+  /// Expect only refspec syntax characters of a message to be encoded
+  fn encode_message_syntax_chars() {
+    assert_eq!("Fix%20typo%2C%20again", encode_message("Fix typo, again"));
+    assert_eq!("100%25%20done%5Fnow", encode_message("100% done_now"));
+    assert_eq!("a%2Bb", encode_message("a+b"));
+    assert_eq!(
+      "see:jane.doe@example.com/x",
+      encode_message("see:jane.doe@example.com/x")
+    );
+    assert_eq!("caf\u{e9}", encode_message("caf\u{e9}"));
+  }
+}
@@ -1,5 +1,5 @@
 use super::prelude::*;
-use crate::config::HttpAuthMethod;
+use crate::config::{CredentialSource, HttpAuthMethod};
 
 pub fn cli() -> App<'static, 'static> {
   SubCommand::with_name("add")
@@ -38,15 +38,34 @@ pub fn cli() -> App<'static, 'static> {
         .long("http-auth")
         .takes_value(true)
         .value_name("method")
-        .possible_values(&["basic", "digest"])
+        .possible_values(&["basic", "digest", "cookie", "bearer"])
         .default_value("basic")
-        .help("Use HTTP Basic Authentication or Digest Authentication."),
+        .help(
+          "Use HTTP Basic Authentication, Digest Authentication, \
+                     cookies from the git cookie file or a bearer access token.\n\
+                     Cookies and bearer tokens are only sent by git transports, not with REST API requests.",
+        ),
+    )
+    .arg(
+      Arg::with_name("cookie-file")
+        .long("cookie-file")
+        .takes_value(true)
+        .value_name("path")
+        .help("Cookie file for the cookie auth method. Defaults to git's http.cookiefile or ~/.gitcookies."),
     )
     .arg(
       Arg::with_name("no-ssl-verify")
         .long("no-ssl-verify")
         .help("Do not to verify the SSL certificate for HTTPS."),
     )
+    .args(&super::credential_args())
+    .args(&super::network_args())
+    .args(&super::ssh_args())
+    .arg(
+      Arg::with_name("verify")
+        .long("verify")
+        .help("Test connectivity and credentials before adding the remote."),
+    )
 }
 
 pub fn exec(config: &mut CliConfig, args: Option<&ArgMatches>) -> Result<(), failure::Error> {
@@ -58,31 +77,90 @@ pub fn exec(config: &mut CliConfig, args: Option<&ArgMatches>) -> Result<(), fai
   let http_password = args.value_of("password").map(|s| s.to_owned());
   let no_ssl_verify = args.is_present("no-ssl-verify");
   let http_auth: HttpAuthMethod = args.value_of("http-auth").unwrap().parse()?;
+  let mut credential: CredentialSource = args.value_of("credential").unwrap_or("plaintext").parse()?;
 
   if config.user.settings.remotes.contains_key(name) {
     return Err(failure::err_msg(format!("remote '{}' already exists.", name)));
   }
+  if http_password.is_some() && !credential.is_plaintext() && credential != CredentialSource::GitCredential {
+    return Err(failure::err_msg(format!(
+      "--http-password cannot be used with the '{}' credential source",
+      credential
+    )));
+  }
 
   let username = match username {
     Some(u) => u,
     None => super::prompt_username(&mut config.stdout, name)?,
   };
 
-  let http_password = match http_password {
-    Some(p) => p,
-    None => super::prompt_http_password(name)?,
+  let http_password = match credential {
+    _ if http_auth == HttpAuthMethod::Cookie || http_auth == HttpAuthMethod::Bearer => String::new(),
+    CredentialSource::Plaintext | CredentialSource::GitCredential => match http_password {
+      Some(p) => p,
+      None => {
+        let password = super::prompt_http_password(name)?;
+        let question = "Store the HTTP password with the git credential helper instead of the configuration file?";
+        if credential.is_plaintext() && util::prompt_confirm(&mut config.stdout, question)? {
+          credential = CredentialSource::GitCredential;
+        }
+        password
+      }
+    },
+    _ => String::new(),
   };
 
-  config.user.settings.remotes.insert(
-    name.into(),
-    RemoteOpts {
-      url: url.to_owned(),
-      username,
-      http_password,
-      http_auth,
-      no_ssl_verify,
+  // the password or token is handed to the credential helper only once the remote is verified
+  let mut store_with_helper = credential == CredentialSource::GitCredential;
+  let mut remote = RemoteOpts {
+    url: url.to_owned(),
+    username,
+    http_password,
+    http_auth,
+    no_ssl_verify,
+    ca_bundle: None,
+    proxy: None,
+    no_proxy: None,
+    credential: if store_with_helper {
+      CredentialSource::Plaintext
+    } else {
+      credential
     },
-  );
+    password_env: args.value_of("password-env").map(|v| v.to_owned()),
+    password_command: args.value_of("password-command").map(|c| c.to_owned()),
+    cookie_file: args.value_of("cookie-file").map(|f| f.to_owned()),
+    token: args.value_of("token").map(|t| t.to_owned()),
+    token_env: args.value_of("token-env").map(|v| v.to_owned()),
+    token_command: args.value_of("token-command").map(|c| c.to_owned()),
+    transport: Transport::Rest,
+    ssh: None,
+  };
+  super::apply_network_args(&mut remote, args)?;
+  super::apply_ssh_args(&mut remote, args)?;
+  if remote.http_auth == HttpAuthMethod::Bearer
+    && remote.token.is_none()
+    && remote.token_env.is_none()
+    && remote.token_command.is_none()
+  {
+    remote.token = Some(super::prompt_token(name)?);
+    let question = "Store the access token with the git credential helper instead of the configuration file?";
+    store_with_helper = store_with_helper || util::prompt_confirm(&mut config.stdout, question)?;
+  }
+  if args.is_present("verify") {
+    super::test::test_remote(config, name, &remote)?;
+  }
+  if store_with_helper {
+    let secret = match remote.http_auth {
+      HttpAuthMethod::Bearer => remote.token.take().unwrap_or_default(),
+      _ => std::mem::take(&mut remote.http_password),
+    };
+    if !secret.is_empty() {
+      util::credential::git_credential_approve(&remote.url, &remote.username, &secret)?;
+    }
+    remote.credential = CredentialSource::GitCredential;
+  }
+
+  config.user.settings.remotes.insert(name.into(), remote);
   config.user.store()?;
 
   Ok(())
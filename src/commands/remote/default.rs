@@ -16,6 +16,9 @@ pub fn exec(config: &mut CliConfig, args: Option<&ArgMatches>) -> Result<(), fai
     set(config, remote)?
   } else {
     if let Some(default) = config.user.settings.default_remote_verify() {
+      if config.format != OutputFormat::Text {
+        return output::write_record(config, &Record::new().field("default", default));
+      }
       writeln!(config.stdout, "{}", default)?;
     } else {
       return Err(failure::err_msg("no default remote"));
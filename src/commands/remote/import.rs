@@ -0,0 +1,290 @@
+use super::prelude::*;
+use super::show;
+use crate::config::{CredentialSource, HttpAuthMethod};
+use git2::Repository;
+use std::io::Write;
+use std::path::Path;
+
+pub fn cli() -> App<'static, 'static> {
+  SubCommand::with_name("import")
+    .about("Create a remote from the current git repository.")
+    .template("{about}\n\nUSAGE:\n    {usage}\n\n{all-args}\n\n{after-help}")
+    .after_help(
+      "Without options, the .gitreview file is used if present, otherwise the 'origin' git remote.\n\
+       The repository is also mapped to the remote and its gerrit project, \
+       so that later commands default to them.",
+    )
+    .setting(clap::AppSettings::DeriveDisplayOrder)
+    .arg(Arg::with_name("name").help("Remote name. Defaults to the gerrit host name."))
+    .arg(
+      Arg::with_name("from-gitreview")
+        .long("from-gitreview")
+        .conflicts_with("from-git-remote")
+        .help("Import from the .gitreview file of the repository."),
+    )
+    .arg(
+      Arg::with_name("from-git-remote")
+        .long("from-git-remote")
+        .takes_value(true)
+        .value_name("git-remote")
+        .help("Import from the URL of a git remote, e.g. origin."),
+    )
+    .arg(
+      Arg::with_name("url-path")
+        .long("url-path")
+        .takes_value(true)
+        .value_name("path")
+        .help(
+          "Path gerrit is served under, e.g. /r for https://host/r/my/project. \
+           Asked for if it cannot be told from the URL of the git remote.",
+        ),
+    )
+    .arg(
+      Arg::with_name("username")
+        .long("username")
+        .short("u")
+        .takes_value(true)
+        .value_name("id")
+        .help("Username for login."),
+    )
+    .arg(
+      Arg::with_name("password")
+        .long("http-password")
+        .short("p")
+        .takes_value(true)
+        .value_name("string")
+        .help(
+          "HTTP password. Can be generated in gerrit user settings menu.\n\
+                     Note: this password is saved in plain text in the configuration file.",
+        ),
+    )
+    .arg(
+      Arg::with_name("yes")
+        .long("yes")
+        .short("y")
+        .help("Create the remote without asking for confirmation."),
+    )
+}
+
+/// Gerrit server and project figured out from the repository
+struct Imported {
+  host: String,
+  url: String,
+  project: Option<String>,
+  username: Option<String>,
+}
+
+pub fn exec(config: &mut CliConfig, args: Option<&ArgMatches>) -> Result<(), failure::Error> {
+  let args = args.unwrap();
+  let verbose: Verbosity = args.occurrences_of("verbose").into();
+
+  let repo = Repository::discover(std::env::current_dir()?)?;
+  let workdir = repo
+    .workdir()
+    .ok_or_else(|| failure::err_msg("cannot import from a bare repository"))?
+    .to_owned();
+  let gitreview = workdir.join(".gitreview");
+
+  let url_path = args.value_of("url-path");
+  let ask = !args.is_present("yes") && config.format == OutputFormat::Text;
+  let imported = if let Some(git_remote) = args.value_of("from-git-remote") {
+    from_git_remote(config, &repo, git_remote, url_path, ask)?
+  } else if args.is_present("from-gitreview") || gitreview.exists() {
+    from_gitreview(&gitreview)?
+  } else {
+    from_git_remote(config, &repo, "origin", url_path, ask)?
+  };
+
+  // Reuse an existing remote of the same server, only mapping the repository to it
+  let existing = config
+    .user
+    .settings
+    .remotes
+    .iter()
+    .find(|(_, remote)| remote.url.trim_end_matches('/') == imported.url)
+    .map(|(name, _)| name.clone());
+
+  let name = match (&existing, args.value_of("name")) {
+    (Some(existing), _) => existing.clone(),
+    (None, Some(name)) => name.to_owned(),
+    (None, None) => imported.host.clone(),
+  };
+
+  if existing.is_none() {
+    if config.user.settings.remotes.contains_key(&name) {
+      return Err(failure::err_msg(format!("remote '{}' already exists.", name)));
+    }
+    if !args.is_present("yes") {
+      let question = format!("Create remote '{}' for {}?", name, imported.url);
+      if !util::prompt_confirm(&mut config.stdout, &question)? {
+        return Ok(());
+      }
+    }
+    let username = match args.value_of("username").map(|u| u.to_owned()).or(imported.username) {
+      Some(u) => u,
+      None => super::prompt_username(&mut config.stdout, &name)?,
+    };
+    let mut credential = CredentialSource::Plaintext;
+    let http_password = match args.value_of("password") {
+      Some(p) => p.to_owned(),
+      None => {
+        let password = super::prompt_http_password(&name)?;
+        let question = "Store the HTTP password with the git credential helper instead of the configuration file?";
+        if util::prompt_confirm(&mut config.stdout, question)? {
+          util::credential::git_credential_approve(&imported.url, &username, &password)?;
+          credential = CredentialSource::GitCredential;
+          String::new()
+        } else {
+          password
+        }
+      }
+    };
+    let remote = RemoteOpts {
+      url: imported.url.clone(),
+      username,
+      http_password,
+      http_auth: HttpAuthMethod::Basic,
+      no_ssl_verify: false,
+      ca_bundle: None,
+      proxy: None,
+      no_proxy: None,
+      credential,
+      password_env: None,
+      password_command: None,
+      cookie_file: None,
+      token: None,
+      token_env: None,
+      token_command: None,
+      transport: Transport::Rest,
+      ssh: None,
+    };
+    config.user.settings.remotes.insert(name.clone(), remote);
+    config.user.store()?;
+  }
+
+  let mut repo_config = repo.config()?;
+  repo_config.set_str("ger.remote", &name)?;
+  if let Some(project) = &imported.project {
+    repo_config.set_str("ger.project", project)?;
+  }
+
+  if config.format != OutputFormat::Text {
+    let remote = config.user.settings.remotes.get_key_value(&name).unwrap();
+    let record = show::record(config, remote, verbose).field("project", &imported.project);
+    return output::write_record(config, &record);
+  }
+  match existing {
+    Some(_) => writeln!(config.stdout, "Using existing remote '{}'", name)?,
+    None => writeln!(config.stdout, "Created remote '{}'", name)?,
+  }
+  if let Some(project) = &imported.project {
+    writeln!(config.stdout, "Mapped repository to project '{}'", project)?;
+  }
+
+  Ok(())
+}
+
+/// Ask for the path gerrit is served under, when it cannot be told apart from the project in a URL
+fn prompt_url_path(config: &mut CliConfig, url: &str) -> Result<String, failure::Error> {
+  write!(
+    config.stdout,
+    "Path gerrit is served under in {}, if any (e.g. /r): ",
+    url
+  )?;
+  config.stdout.flush()?;
+  let mut input = String::new();
+  std::io::stdin().read_line(&mut input)?;
+  Ok(input.trim().into())
+}
+
+/// Import from a `.gitreview` file, which looks like:
+///
+/// ```text
+/// [gerrit]
+/// host=review.example.com
+/// port=29418
+/// project=my/project.git
+/// ```
+fn from_gitreview(path: &Path) -> Result<Imported, failure::Error> {
+  let contents =
+    std::fs::read_to_string(path).map_err(|e| failure::err_msg(format!("cannot read {}: {}", path.display(), e)))?;
+  let mut section = String::new();
+  let mut host = None;
+  let mut project = None;
+  for line in contents.lines().map(|l| l.trim()) {
+    if line.starts_with('[') && line.ends_with(']') {
+      section = line[1..line.len() - 1].trim().to_lowercase();
+    } else if section == "gerrit" {
+      let mut parts = line.splitn(2, '=');
+      let key = parts.next().unwrap_or("").trim();
+      let value = parts.next().unwrap_or("").trim();
+      match key {
+        "host" => host = Some(value.to_owned()),
+        "project" => project = Some(value.trim_end_matches(".git").to_owned()),
+        _ => {}
+      }
+    }
+  }
+  let host = host.ok_or_else(|| failure::err_msg(format!("no gerrit host in {}", path.display())))?;
+  Ok(Imported {
+    url: format!("https://{}", host),
+    host,
+    project,
+    username: None,
+  })
+}
+
+/// Import from the URL of a git remote, either ssh, scp-like or http(s).
+///
+/// The path gerrit is served under is kept in the URL of http remotes, e.g. `/r` of `https://host/r/a/my/project`.
+/// Without an `/a/` segment it is taken from `url_path`, or else asked for if `ask` is set.
+fn from_git_remote(
+  config: &mut CliConfig, repo: &Repository, git_remote: &str, url_path: Option<&str>, ask: bool,
+) -> Result<Imported, failure::Error> {
+  let remote = repo
+    .find_remote(git_remote)
+    .map_err(|_| failure::err_msg(format!("no such git remote: {}", git_remote)))?;
+  let url = remote
+    .url()
+    .ok_or_else(|| failure::err_msg(format!("git remote '{}' has no URL", git_remote)))?;
+
+  let (scheme, rest) = match url.find("://") {
+    Some(index) => (&url[..index], &url[index + 3..]),
+    None => ("ssh", url),
+  };
+  let authority = rest
+    .split(|c| c == '/' || (scheme == "ssh" && c == ':'))
+    .next()
+    .unwrap_or("");
+  let (username, hostport) = match authority.rfind('@') {
+    Some(index) => (Some(authority[..index].to_owned()), &authority[index + 1..]),
+    None => (None, authority),
+  };
+  let host = hostport.split(':').next().unwrap_or("").to_owned();
+  if host.is_empty() {
+    return Err(failure::err_msg(format!(
+      "cannot figure out the gerrit host from '{}'",
+      url
+    )));
+  }
+
+  let (url, project) = match scheme {
+    "http" | "https" => {
+      let path = rest.find('/').map(|index| &rest[index..]).unwrap_or("");
+      let mut url_path = url_path.map(|p| p.to_owned());
+      if url_path.is_none() && ask && path.trim_matches('/').contains('/') && !path.contains("/a/") {
+        url_path = Some(prompt_url_path(config, &util::redact_url(url))?);
+      }
+      let (served, project) = util::git::split_http_path(path, url_path.as_deref());
+      (format!("{}://{}{}", scheme, hostport, served), project)
+    }
+    _ => (format!("https://{}", host), util::git::project_from_url(url)),
+  };
+  Ok(Imported {
+    host,
+    url,
+    project,
+    // usernames of http URLs might include the password
+    username: username.map(|u| u.split(':').next().unwrap_or("").to_owned()),
+  })
+}
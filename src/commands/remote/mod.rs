@@ -3,23 +3,36 @@ use std::io::Write;
 use termcolor::StandardStream;
 
 mod prelude {
-  pub use crate::config::{CliConfig, RemoteOpts, Verbosity};
+  pub use crate::config::{CliConfig, OutputFormat, RemoteOpts, SshOpts, Transport, Verbosity};
+  pub use crate::output::{self, Record};
   pub use crate::util;
   pub use clap::{App, Arg, ArgMatches, SubCommand};
 }
 
 mod add;
 mod default;
+mod import;
 mod remove;
 mod rename;
+mod set;
 mod show;
+mod test;
 
 /// Build the CLI
 pub fn cli() -> App<'static, 'static> {
   SubCommand::with_name("remote")
     .about("Manage gerrit remote servers.")
     .template("{about}\n\nUSAGE:\n    {usage}\n\n{all-args}")
-    .subcommands(vec![add::cli(), show::cli(), rename::cli(), remove::cli(), default::cli()])
+    .subcommands(vec![
+      add::cli(),
+      show::cli(),
+      set::cli(),
+      import::cli(),
+      rename::cli(),
+      remove::cli(),
+      default::cli(),
+      test::cli(),
+    ])
 }
 
 /// Execute the remote command
@@ -29,13 +42,64 @@ pub fn exec(config: &mut CliConfig, args: Option<&ArgMatches>) -> Result<(), fai
     ("add", subargs) => add::exec(config, subargs),
     ("show", subargs) => show::exec(config, subargs),
     ("", _) => show::show_list(config, args.occurrences_of("verbose").into()),
+    ("set", subargs) => set::exec(config, subargs),
+    ("import", subargs) => import::exec(config, subargs),
     ("rename", subargs) => rename::exec(config, subargs),
     ("remove", subargs) => remove::exec(config, subargs),
     ("default", subargs) => default::exec(config, subargs),
+    ("test", subargs) => test::exec(config, subargs),
     _ => Ok(()),
   }
 }
 
+static CREDENTIAL_VALUES: &[&str] = &["plaintext", "git_credential", "netrc", "env", "command"];
+
+/// Arguments selecting where the HTTP password of a remote comes from
+fn credential_args() -> Vec<Arg<'static, 'static>> {
+  vec![
+    Arg::with_name("credential")
+      .long("credential")
+      .takes_value(true)
+      .value_name("source")
+      .possible_values(CREDENTIAL_VALUES)
+      .help(
+        "Where to get the HTTP password from: the configuration file (plaintext), \
+                   the git credential helper, ~/.netrc, an environment variable or a command.",
+      ),
+    Arg::with_name("password-env")
+      .long("password-env")
+      .takes_value(true)
+      .value_name("VAR")
+      .help("Environment variable holding the HTTP password. Defaults to GER_HTTP_PASSWORD."),
+    Arg::with_name("password-command")
+      .long("password-command")
+      .takes_value(true)
+      .value_name("command")
+      .help("Shell command whose output is the HTTP password, e.g. 'pass show gerrit'."),
+    Arg::with_name("token")
+      .long("token")
+      .takes_value(true)
+      .value_name("string")
+      .conflicts_with_all(&["token-env", "token-command"])
+      .help(
+        "Access token for the bearer auth method.\n\
+                   Note: this token is saved in plain text in the configuration file, \
+                   unless the credential source is the git credential helper.",
+      ),
+    Arg::with_name("token-env")
+      .long("token-env")
+      .takes_value(true)
+      .value_name("VAR")
+      .conflicts_with("token-command")
+      .help("Environment variable holding the access token for the bearer auth method."),
+    Arg::with_name("token-command")
+      .long("token-command")
+      .takes_value(true)
+      .value_name("command")
+      .help("Shell command whose output is the access token for the bearer auth method."),
+  ]
+}
+
 /// Prompt for Username for given remote
 fn prompt_username(stdout: &mut StandardStream, remote: &str) -> Result<String, failure::Error> {
   write!(stdout, "Username for '{}': ", remote)?;
@@ -45,6 +109,126 @@ fn prompt_username(stdout: &mut StandardStream, remote: &str) -> Result<String,
   Ok(input.trim().into())
 }
 
+/// Arguments of the TLS and proxy settings of a remote
+fn network_args() -> Vec<Arg<'static, 'static>> {
+  vec![
+    Arg::with_name("ca-bundle")
+      .long("ca-bundle")
+      .takes_value(true)
+      .value_name("file")
+      .help("CA certificates bundle to verify the server's certificate with, e.g. of a corporate proxy."),
+    Arg::with_name("proxy")
+      .long("proxy")
+      .takes_value(true)
+      .value_name("url")
+      .help("HTTP(S) proxy URL. Defaults to the HTTPS_PROXY or HTTP_PROXY environment variables."),
+    Arg::with_name("no-proxy")
+      .long("no-proxy")
+      .takes_value(true)
+      .value_name("hosts")
+      .help("Comma-separated hosts not to use the proxy for. Defaults to the NO_PROXY environment variable."),
+  ]
+}
+
+/// Update the TLS and proxy settings of a remote from the `network_args` given
+fn apply_network_args(remote: &mut RemoteOpts, args: &ArgMatches) -> Result<(), failure::Error> {
+  // absolute paths, so that the remote works from any directory
+  let path = |arg: &str| -> Result<Option<String>, failure::Error> {
+    match args.value_of(arg) {
+      Some(path) => match std::fs::canonicalize(path) {
+        Ok(path) if path.is_file() => Ok(Some(path.to_string_lossy().into_owned())),
+        _ => Err(failure::err_msg(format!("no such file: {}", path))),
+      },
+      None => Ok(None),
+    }
+  };
+  if let Some(ca_bundle) = path("ca-bundle")? {
+    remote.ca_bundle = Some(ca_bundle);
+  }
+  if let Some(proxy) = args.value_of("proxy") {
+    remote.proxy = Some(proxy.to_owned());
+  }
+  if let Some(no_proxy) = args.value_of("no-proxy") {
+    remote.no_proxy = Some(no_proxy.to_owned());
+  }
+  Ok(())
+}
+
+/// Arguments selecting the transport of a remote and its SSH connection
+fn ssh_args() -> Vec<Arg<'static, 'static>> {
+  vec![
+    Arg::with_name("transport")
+      .long("transport")
+      .takes_value(true)
+      .possible_values(&["rest", "ssh"])
+      .help("Run commands which support it over the REST API or the gerrit SSH commands."),
+    Arg::with_name("ssh-host")
+      .long("ssh-host")
+      .takes_value(true)
+      .value_name("host")
+      .help("SSH host of the gerrit server. Defaults to the host of the remote URL."),
+    Arg::with_name("ssh-port")
+      .long("ssh-port")
+      .takes_value(true)
+      .value_name("port")
+      .validator(util::validate::is_u16_range)
+      .help("SSH port of the gerrit server. Defaults to 29418."),
+    Arg::with_name("ssh-user")
+      .long("ssh-user")
+      .takes_value(true)
+      .value_name("user")
+      .help("SSH user. Defaults to the username of the remote."),
+    Arg::with_name("ssh-identity")
+      .long("ssh-identity")
+      .takes_value(true)
+      .value_name("file")
+      .help("SSH identity (private key) file."),
+  ]
+}
+
+/// Update the transport and SSH options of a remote from the `ssh_args` given
+fn apply_ssh_args(remote: &mut RemoteOpts, args: &ArgMatches) -> Result<(), failure::Error> {
+  if let Some(transport) = args.value_of("transport") {
+    remote.transport = transport.parse::<Transport>()?;
+  }
+  let ssh_given = ["ssh-host", "ssh-port", "ssh-user", "ssh-identity"]
+    .iter()
+    .any(|a| args.is_present(a));
+  if remote.ssh.is_none() && (ssh_given || remote.transport == Transport::Ssh) {
+    let rest = remote
+      .url
+      .find("://")
+      .map_or(remote.url.as_str(), |index| &remote.url[index + 3..]);
+    let host = rest.split(|c| c == '/' || c == ':').next().unwrap_or("");
+    remote.ssh = Some(SshOpts {
+      host: host.to_owned(),
+      ..SshOpts::default()
+    });
+  }
+  if let Some(ssh) = remote.ssh.as_mut() {
+    if let Some(host) = args.value_of("ssh-host") {
+      ssh.host = host.to_owned();
+    }
+    if let Some(port) = args.value_of("ssh-port") {
+      ssh.port = port.parse::<u16>()?;
+    }
+    if let Some(user) = args.value_of("ssh-user") {
+      ssh.user = Some(user.to_owned());
+    }
+    if let Some(identity) = args.value_of("ssh-identity") {
+      ssh.identity_file = Some(identity.to_owned());
+    }
+  }
+  Ok(())
+}
+
+/// Prompt for an access token for given remote
+fn prompt_token(remote: &str) -> Result<String, failure::Error> {
+  let prompt = format!("Access token for '{}': ", remote);
+  let input = rpassword::read_password_from_tty(Some(prompt.as_str()))?;
+  Ok(input.trim().into())
+}
+
 /// Prompt for HTTP Password for given remote
 fn prompt_http_password(remote: &str) -> Result<String, failure::Error> {
   let prompt = format!("HTTP-Password for '{}': ", remote);
@@ -12,13 +12,20 @@ pub fn cli() -> App<'static, 'static> {
 pub fn exec(config: &mut CliConfig, args: Option<&ArgMatches>) -> Result<(), failure::Error> {
   let args = args.unwrap();
   let remotes = args.values_of("remote").unwrap();
+  let mut records = Vec::new();
   for remote in remotes {
+    let removed = config.user.settings.remotes.remove(remote).is_some();
+    if config.format != OutputFormat::Text {
+      records.push(Record::new().field("name", remote).field("removed", removed));
+      continue;
+    }
     let mut stdout = config.stdout.lock();
-    match config.user.settings.remotes.remove(remote) {
-      Some(_) => writeln!(stdout, "removed remote {}", remote)?,
-      None => writeln!(stdout, "fatal: no such remote: {}", remote)?,
+    match removed {
+      true => writeln!(stdout, "removed remote {}", remote)?,
+      false => writeln!(stdout, "fatal: no such remote: {}", remote)?,
     };
   }
   config.user.store()?;
+  output::write_records(config, &records)?;
   Ok(())
 }
@@ -40,6 +40,9 @@ pub fn exec(config: &mut CliConfig, args: Option<&ArgMatches>) -> Result<(), fai
   }
 
   config.user.store()?;
+  if config.format != OutputFormat::Text {
+    return output::write_record(config, &Record::new().field("name", new).field("old_name", old));
+  }
   if verbose >= Verbosity::Verbose {
     writeln!(config.stdout, "renamed remote '{}' to '{}'", old, new)?;
   }
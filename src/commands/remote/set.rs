@@ -0,0 +1,204 @@
+use super::prelude::*;
+use super::show;
+use crate::config::{CredentialSource, HttpAuthMethod};
+use clap::ArgGroup;
+use std::io::Write;
+
+pub fn cli() -> App<'static, 'static> {
+  SubCommand::with_name("set")
+    .visible_alias("edit")
+    .about("Update settings of a remote.")
+    .template("{about}\n\nUSAGE:\n    {usage}\n\n{all-args}\n\n{after-help}")
+    .after_help("EXAMPLE:\n    ger remote set mygerrit --http-auth digest --username john")
+    .setting(clap::AppSettings::DeriveDisplayOrder)
+    .arg(Arg::with_name("name").required(true).help("Remote name."))
+    .arg(
+      Arg::with_name("url")
+        .long("url")
+        .takes_value(true)
+        .validator(util::validate::is_url_http_https)
+        .help("Remote URL including protocol. e.g. 'https://mygerrit.com'."),
+    )
+    .arg(
+      Arg::with_name("username")
+        .long("username")
+        .short("u")
+        .takes_value(true)
+        .value_name("id")
+        .help("Username for login."),
+    )
+    .arg(
+      Arg::with_name("password")
+        .long("http-password")
+        .visible_alias("password")
+        .short("p")
+        .takes_value(true)
+        .value_name("string")
+        .help(
+          "HTTP password. Can be generated in gerrit user settings menu.\n\
+                     Note: this password is saved in plain text in the configuration file.",
+        ),
+    )
+    .arg(
+      Arg::with_name("http-auth")
+        .long("http-auth")
+        .takes_value(true)
+        .value_name("method")
+        .possible_values(&["basic", "digest", "cookie", "bearer"])
+        .help(
+          "Use HTTP Basic Authentication, Digest Authentication, \
+                     cookies from the git cookie file or a bearer access token.\n\
+                     Cookies and bearer tokens are only sent by git transports, not with REST API requests.",
+        ),
+    )
+    .arg(
+      Arg::with_name("cookie-file")
+        .long("cookie-file")
+        .takes_value(true)
+        .value_name("path")
+        .help("Cookie file for the cookie auth method. Defaults to git's http.cookiefile or ~/.gitcookies."),
+    )
+    .arg(
+      Arg::with_name("ssl-verify")
+        .long("ssl-verify")
+        .overrides_with("no-ssl-verify")
+        .help("Verify the SSL certificate for HTTPS."),
+    )
+    .arg(
+      Arg::with_name("no-ssl-verify")
+        .long("no-ssl-verify")
+        .overrides_with("ssl-verify")
+        .help("Do not to verify the SSL certificate for HTTPS."),
+    )
+    .args(&super::credential_args())
+    .args(&super::network_args())
+    .args(&super::ssh_args())
+    .group(
+      ArgGroup::with_name("settings")
+        .args(&[
+          "url",
+          "username",
+          "password",
+          "http-auth",
+          "ssl-verify",
+          "no-ssl-verify",
+          "cookie-file",
+          "ca-bundle",
+          "proxy",
+          "no-proxy",
+          "credential",
+          "password-env",
+          "password-command",
+          "token",
+          "token-env",
+          "token-command",
+          "transport",
+          "ssh-host",
+          "ssh-port",
+          "ssh-user",
+          "ssh-identity",
+        ])
+        .multiple(true)
+        .required(true),
+    )
+}
+
+pub fn exec(config: &mut CliConfig, args: Option<&ArgMatches>) -> Result<(), failure::Error> {
+  let args = args.unwrap();
+  let verbose: Verbosity = args.occurrences_of("verbose").into();
+  let name = args.value_of("name").unwrap();
+
+  let remote = match config.user.settings.remotes.get_mut(name) {
+    Some(remote) => remote,
+    None => return Err(failure::err_msg(format!("no such remote: {}", name))),
+  };
+  if let Some(url) = args.value_of("url") {
+    remote.url = url.to_owned();
+  }
+  if let Some(username) = args.value_of("username") {
+    remote.username = username.to_owned();
+  }
+  if let Some(credential) = args.value_of("credential") {
+    remote.credential = credential.parse::<CredentialSource>()?;
+  }
+  if let Some(password_env) = args.value_of("password-env") {
+    remote.password_env = Some(password_env.to_owned());
+  }
+  if let Some(password_command) = args.value_of("password-command") {
+    remote.password_command = Some(password_command.to_owned());
+  }
+  if let Some(http_password) = args.value_of("password") {
+    match remote.credential {
+      CredentialSource::Plaintext => remote.http_password = http_password.to_owned(),
+      CredentialSource::GitCredential => {
+        util::credential::git_credential_approve(&remote.url, &remote.username, http_password)?
+      }
+      _ => {
+        return Err(failure::err_msg(
+          "the HTTP password is read from the credential source of the remote",
+        ))
+      }
+    }
+  }
+  // keep the plaintext password out of the configuration file once another source is used,
+  // handing it over to the git credential helper unless a new one was given
+  if !remote.credential.is_plaintext() && !remote.http_password.is_empty() {
+    if remote.credential == CredentialSource::GitCredential && !args.is_present("password") {
+      util::credential::git_credential_approve(&remote.url, &remote.username, &remote.http_password)?;
+    }
+    remote.http_password.clear();
+  }
+  if let Some(http_auth) = args.value_of("http-auth") {
+    remote.http_auth = http_auth.parse::<HttpAuthMethod>()?;
+  }
+  if let Some(cookie_file) = args.value_of("cookie-file") {
+    remote.cookie_file = Some(cookie_file.to_owned());
+  }
+  // a new token source replaces the others, which would otherwise take precedence
+  if let Some(token) = args.value_of("token") {
+    remote.token_env = None;
+    remote.token_command = None;
+    match remote.credential {
+      CredentialSource::GitCredential => {
+        util::credential::git_credential_approve(&remote.url, &remote.username, token)?;
+        remote.token = None;
+      }
+      _ => remote.token = Some(token.to_owned()),
+    }
+  }
+  if let Some(token_env) = args.value_of("token-env") {
+    remote.token = None;
+    remote.token_command = None;
+    remote.token_env = Some(token_env.to_owned());
+  }
+  if let Some(token_command) = args.value_of("token-command") {
+    remote.token = None;
+    remote.token_env = None;
+    remote.token_command = Some(token_command.to_owned());
+  }
+  // likewise keep the plaintext token out of the configuration file
+  if remote.credential == CredentialSource::GitCredential && remote.http_auth == HttpAuthMethod::Bearer {
+    if let Some(token) = remote.token.take() {
+      util::credential::git_credential_approve(&remote.url, &remote.username, &token)?;
+    }
+  }
+  super::apply_network_args(remote, args)?;
+  super::apply_ssh_args(remote, args)?;
+  if args.is_present("ssl-verify") {
+    remote.no_ssl_verify = false;
+  }
+  if args.is_present("no-ssl-verify") {
+    remote.no_ssl_verify = true;
+  }
+
+  config.user.store()?;
+  if config.format != OutputFormat::Text {
+    let remote = config.user.settings.remotes.get_key_value(name).unwrap();
+    return output::write_record(config, &show::record(config, remote, verbose));
+  }
+  if verbose >= Verbosity::Verbose {
+    writeln!(config.stdout, "updated remote '{}'", name)?;
+  }
+
+  Ok(())
+}
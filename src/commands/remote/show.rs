@@ -1,4 +1,5 @@
 use super::prelude::*;
+use crate::config::HttpAuthMethod;
 use std::io::Write;
 use termcolor::{Color, ColorSpec, WriteColor};
 
@@ -22,6 +23,17 @@ pub fn exec(config: &mut CliConfig, args: Option<&ArgMatches>) -> Result<(), fai
 
 /// Show basic information about cofigured remotes
 pub fn show_list(config: &CliConfig, verbose: Verbosity) -> Result<(), failure::Error> {
+  if config.format != OutputFormat::Text {
+    let records: Vec<Record> = config
+      .user
+      .settings
+      .remotes
+      .iter()
+      .map(|remote| record(config, remote, verbose))
+      .collect();
+    return output::write_records(config, &records);
+  }
+
   let mut name_maxlen = 0;
   let mut url_maxlen = 0;
   // compute format variables
@@ -65,17 +77,113 @@ where
   I: IntoIterator<Item = T>,
   T: Into<String>,
 {
+  let mut records = Vec::new();
   for name in iter_remotes {
     let name = name.into();
     if let Some(remote) = config.user.settings.remotes.get(&name) {
-      show_remote(config, (name.as_str(), remote), verbose.clone())?;
+      if config.format == OutputFormat::Text {
+        show_remote(config, (name.as_str(), remote), verbose.clone())?;
+      } else {
+        records.push(record(config, (&name, remote), verbose));
+      }
     } else {
       return Err(failure::err_msg(format!("no such remote '{}'", name)));
     }
   }
+  if config.format != OutputFormat::Text {
+    output::write_records(config, &records)?;
+  }
   Ok(())
 }
 
+/// Build the output record of a remote, unset settings being null.
+/// The HTTP password and access token are only included with high verbosity, the cookie with verbosity.
+pub fn record(config: &CliConfig, remote: (&String, &RemoteOpts), verbose: Verbosity) -> Record {
+  let default = config.user.settings.default_remote_verify() == Some(remote.0.as_str());
+  let cookie_auth = remote.1.http_auth == HttpAuthMethod::Cookie;
+  let cookie_path = if remote.1.cookie_file.is_some() || cookie_auth {
+    Some(cookie_file(remote.1))
+  } else {
+    None
+  };
+  let mut record = Record::new()
+    .field("name", remote.0)
+    .field("default", default)
+    .field("url", &remote.1.url)
+    .field("username", &remote.1.username);
+  if verbose >= Verbosity::High {
+    let http_password = Some(&remote.1.http_password).filter(|_| remote.1.credential.is_plaintext());
+    record = record.field("http_password", http_password);
+  }
+  record = record
+    .field("credential", remote.1.credential.to_string())
+    .field("password_env", &remote.1.password_env)
+    .field("password_command", &remote.1.password_command)
+    .field("http_auth", remote.1.http_auth.to_string());
+  if verbose >= Verbosity::High {
+    record = record.field("token", &remote.1.token);
+  }
+  record = record
+    .field("token_env", &remote.1.token_env)
+    .field("token_command", &remote.1.token_command)
+    .field("cookie_file", cookie_path);
+  if verbose >= Verbosity::Verbose {
+    record = record.field("cookie", Some(remote.1).filter(|_| cookie_auth).map(selected_cookie));
+  }
+  record
+    .field("no_ssl_verify", remote.1.no_ssl_verify)
+    .field("ca_bundle", &remote.1.ca_bundle)
+    .field("proxy", remote.1.proxy.as_deref().map(util::redact_url))
+    .field("no_proxy", &remote.1.no_proxy)
+    .field("transport", remote.1.transport.to_string())
+    .field("ssh", remote.1.ssh.as_ref().map(|ssh| ssh_value(remote.1, ssh)))
+}
+
+/// TLS and proxy settings of a remote which are set
+fn network_settings(remote: &RemoteOpts) -> Vec<(&'static str, String)> {
+  let mut settings = Vec::new();
+  let paths = [("ca_bundle", &remote.ca_bundle), ("no_proxy", &remote.no_proxy)];
+  for (name, value) in paths.iter() {
+    if let Some(value) = value {
+      settings.push((*name, value.clone()));
+    }
+  }
+  if let Some(proxy) = &remote.proxy {
+    settings.push(("proxy", util::redact_url(proxy)));
+  }
+  settings
+}
+
+/// SSH connection of a remote, with the user defaulted
+fn ssh_value(remote: &RemoteOpts, ssh: &SshOpts) -> serde_json::Value {
+  serde_json::json!({
+    "host": ssh.host,
+    "port": ssh.port,
+    "user": ssh.user.as_ref().unwrap_or(&remote.username),
+    "identity_file": ssh.identity_file,
+  })
+}
+
+/// Cookie file of a remote, or why it is unavailable
+fn cookie_file(remote: &RemoteOpts) -> String {
+  match util::cookies::cookie_file(remote) {
+    Ok(path) => path.display().to_string(),
+    Err(e) => format!("unavailable: {}", e),
+  }
+}
+
+/// Describe the cookie selected for a remote with cookie authentication, without its value
+fn selected_cookie(remote: &RemoteOpts) -> String {
+  match util::cookies::find_cookies(remote) {
+    Ok(cookies) => cookies
+      .iter()
+      .map(|cookie| format!("{} ({}{})", cookie.name, cookie.domain, cookie.path))
+      .collect::<Vec<_>>()
+      .join(", "),
+    Err(e) => format!("none: {}", e),
+  }
+}
+
 /// Show information about a given remote
 pub fn show_remote(config: &CliConfig, remote: (&str, &RemoteOpts), verbose: Verbosity) -> Result<(), failure::Error> {
   let mut stdout = config.stdout.lock();
@@ -90,13 +198,51 @@ pub fn show_remote(config: &CliConfig, remote: (&str, &RemoteOpts), verbose: Ver
     "{} remote: {}\n  url: {}\n  username: {}",
     star, remote.0, remote.1.url, remote.1.username
   )?;
-  if verbose >= Verbosity::High {
+  if !remote.1.credential.is_plaintext() {
+    writeln!(stdout, "  credential: {}", remote.1.credential)?;
+  } else if verbose >= Verbosity::High {
     writeln!(stdout, "  http_password: {}", remote.1.http_password)?
   }
+  if let Some(password_env) = &remote.1.password_env {
+    writeln!(stdout, "  password_env: {}", password_env)?;
+  }
+  if let Some(password_command) = &remote.1.password_command {
+    writeln!(stdout, "  password_command: {}", password_command)?;
+  }
   writeln!(stdout, "  http_auth: {}", remote.1.http_auth)?;
+  if verbose >= Verbosity::High {
+    if let Some(token) = &remote.1.token {
+      writeln!(stdout, "  token: {}", token)?;
+    }
+  }
+  if let Some(token_env) = &remote.1.token_env {
+    writeln!(stdout, "  token_env: {}", token_env)?;
+  }
+  if let Some(token_command) = &remote.1.token_command {
+    writeln!(stdout, "  token_command: {}", token_command)?;
+  }
+  if remote.1.cookie_file.is_some() || (verbose >= Verbosity::Verbose && remote.1.http_auth == HttpAuthMethod::Cookie) {
+    writeln!(stdout, "  cookie_file: {}", cookie_file(remote.1))?;
+  }
+  if verbose >= Verbosity::Verbose && remote.1.http_auth == HttpAuthMethod::Cookie {
+    writeln!(stdout, "  cookie: {}", selected_cookie(remote.1))?;
+  }
   if remote.1.no_ssl_verify {
     writeln!(stdout, "  no_ssl_verify: {}", remote.1.no_ssl_verify)?;
   }
+  for (name, value) in network_settings(remote.1) {
+    writeln!(stdout, "  {}: {}", name, value)?;
+  }
+  if !remote.1.transport.is_rest() {
+    writeln!(stdout, "  transport: {}", remote.1.transport)?;
+  }
+  if let Some(ssh) = &remote.1.ssh {
+    let user = ssh.user.as_ref().unwrap_or(&remote.1.username);
+    writeln!(stdout, "  ssh: {}@{}:{}", user, ssh.host, ssh.port)?;
+    if let Some(identity_file) = &ssh.identity_file {
+      writeln!(stdout, "  ssh_identity: {}", identity_file)?;
+    }
+  }
   stdout.write_all(b"\n")?;
   if default {
     stdout.reset()?;
@@ -0,0 +1,233 @@
+use super::prelude::*;
+use crate::config::HttpAuthMethod;
+use crate::handler::get_remote_opts;
+use curl::easy::{Auth, Easy, List};
+use failure::ResultExt;
+use gerlib::accounts::AccountInfo;
+use serde::de::DeserializeOwned;
+use std::io::Write;
+use termcolor::{Color, ColorSpec, WriteColor};
+
+/// Build the CLI for test command
+pub fn cli() -> App<'static, 'static> {
+  SubCommand::with_name("test")
+    .about("Test connectivity and credentials of a remote.")
+    .template("{about}\n\nUSAGE:\n    {usage}\n\n{all-args}")
+    .arg(Arg::with_name("remote").help("Remote name. Defaults to the default remote."))
+}
+
+/// Execute the test command
+pub fn exec(config: &mut CliConfig, args: Option<&ArgMatches>) -> Result<(), failure::Error> {
+  let args = args.unwrap();
+  let remote = get_remote_opts(config, args.value_of("remote"))?.clone();
+  let name = match args.value_of("remote") {
+    Some(name) => name,
+    None => config.user.settings.default_remote_verify().unwrap(),
+  };
+  test_remote(config, name, &remote)
+}
+
+/// Query the server version and the authenticated account of a remote, and report them.
+///
+/// Fails with a distinct error for connection, TLS certificate and authentication problems.
+pub fn test_remote(config: &CliConfig, name: &str, remote: &RemoteOpts) -> Result<(), failure::Error> {
+  let (status, body) = probe(remote, &remote.http_auth, "config/server/version")?;
+  if status != 200 {
+    return Err(status_error(remote, status, &body));
+  }
+  let version: String = parse_response(&body)?;
+  let (status, body) = probe(remote, &remote.http_auth, "a/accounts/self")?;
+  match status {
+    200 => {}
+    401 | 403 => return Err(auth_error(remote, &body)?),
+    _ => return Err(status_error(remote, status, &body)),
+  }
+  let account: AccountInfo = parse_response(&body)?;
+
+  let tls = if !remote.url.starts_with("https://") {
+    "none".to_owned()
+  } else if remote.no_ssl_verify {
+    "not verified".to_owned()
+  } else if let Some(ca_bundle) = &remote.ca_bundle {
+    format!("verified (ca_bundle {})", ca_bundle)
+  } else {
+    "verified".to_owned()
+  };
+  let proxy = match remote.bypasses_proxy() {
+    true => None,
+    false => remote.effective_proxy().map(|proxy| util::redact_url(&proxy)),
+  };
+
+  if config.format != OutputFormat::Text {
+    let record = Record::new()
+      .field("remote", name)
+      .field("url", &remote.url)
+      .field("version", &version)
+      .field("account_id", account.account_id)
+      .field("username", &account.username)
+      .field("name", &account.name)
+      .field("http_auth", remote.http_auth.to_string())
+      .field("tls", &tls)
+      .field("proxy", &proxy);
+    return output::write_record(config, &record);
+  }
+
+  let mut stdout = config.stdout.lock();
+  writeln!(stdout, "Remote:   {} ({})", name, remote.url)?;
+  writeln!(stdout, "Version:  {}", version)?;
+  write!(stdout, "User:     {}", util::account_name(&account))?;
+  if let Some(username) = &account.username {
+    write!(stdout, " ({})", username)?;
+  }
+  writeln!(stdout)?;
+  writeln!(stdout, "Auth:     {}", remote.http_auth)?;
+  write!(stdout, "TLS:      ")?;
+  if !tls.starts_with("verified") {
+    stdout.set_color(ColorSpec::new().set_fg(Some(Color::Yellow)))?;
+  }
+  writeln!(stdout, "{}", tls)?;
+  stdout.reset()?;
+  if let Some(proxy) = &proxy {
+    writeln!(stdout, "Proxy:    {}", proxy)?;
+  }
+  stdout.set_color(ColorSpec::new().set_fg(Some(Color::Green)))?;
+  writeln!(stdout, "OK")?;
+  stdout.reset()?;
+  Ok(())
+}
+
+/// Get a REST API endpoint of a remote with the given authentication method,
+/// returning the HTTP status and body of the response.
+///
+/// The request is made with libcurl directly rather than through gerlib, whose errors
+/// tell neither the HTTP status nor the libcurl error apart.
+fn probe(remote: &RemoteOpts, http_auth: &HttpAuthMethod, endpoint: &str) -> Result<(u32, String), failure::Error> {
+  let mut easy = Easy::new();
+  easy.url(&format!("{}/{}", remote.url.trim_end_matches('/'), endpoint))?;
+  easy.ssl_verify_peer(!remote.no_ssl_verify)?;
+  easy.ssl_verify_host(!remote.no_ssl_verify)?;
+  if let Some(ca_bundle) = &remote.ca_bundle {
+    easy.cainfo(ca_bundle)?;
+  }
+  if let Some(proxy) = &remote.proxy {
+    easy.proxy(proxy)?;
+  }
+  if let Some(no_proxy) = &remote.no_proxy {
+    easy.noproxy(no_proxy)?;
+  }
+  match http_auth {
+    HttpAuthMethod::Basic | HttpAuthMethod::Digest => {
+      easy.username(&remote.username)?;
+      easy.password(&util::credential::http_password(remote)?)?;
+      let mut auth = Auth::new();
+      match http_auth {
+        HttpAuthMethod::Digest => auth.digest(true),
+        _ => auth.basic(true),
+      };
+      easy.http_auth(&auth)?;
+    }
+    HttpAuthMethod::Cookie | HttpAuthMethod::Bearer => {
+      let mut headers = List::new();
+      for header in util::git::remote_headers(remote)? {
+        headers.append(&header)?;
+      }
+      easy.http_headers(headers)?;
+    }
+  }
+
+  let mut body = Vec::new();
+  {
+    let mut transfer = easy.transfer();
+    transfer.write_function(|data| {
+      body.extend_from_slice(data);
+      Ok(data.len())
+    })?;
+    transfer.perform().map_err(|e| request_error(remote, &e))?;
+  }
+  Ok((easy.response_code()?, String::from_utf8_lossy(&body).into_owned()))
+}
+
+/// Parse a JSON response of the REST API, which is prefixed to prevent XSSI
+fn parse_response<T: DeserializeOwned>(body: &str) -> Result<T, failure::Error> {
+  let json = body.trim_start().trim_start_matches(")]}'");
+  Ok(serde_json::from_str(json).context("unexpected response of the REST API")?)
+}
+
+/// Tell a wrong password apart from a wrong authentication scheme by trying the other scheme
+fn auth_error(remote: &RemoteOpts, body: &str) -> Result<failure::Error, failure::Error> {
+  let other = match remote.http_auth {
+    HttpAuthMethod::Basic => HttpAuthMethod::Digest,
+    HttpAuthMethod::Digest => HttpAuthMethod::Basic,
+    HttpAuthMethod::Bearer => {
+      return Ok(failure::err_msg(format!(
+        "authentication with bearer token failed, the token might be invalid or expired ({})",
+        error_reason(body)
+      )))
+    }
+    HttpAuthMethod::Cookie => {
+      return Ok(failure::err_msg(format!(
+        "authentication with cookies from {} failed, the cookie might be expired ({})",
+        util::cookies::cookie_file(remote).map_or_else(|_| "the cookie file".to_owned(), |p| p.display().to_string()),
+        error_reason(body)
+      )))
+    }
+  };
+  let (status, _) = probe(remote, &other, "a/accounts/self")?;
+  if status == 200 {
+    Ok(failure::err_msg(format!(
+      "authentication failed with http_auth '{}' but succeeded with '{}': use --http-auth {}",
+      remote.http_auth, other, other
+    )))
+  } else {
+    Ok(failure::err_msg(format!(
+      "authentication failed for user '{}': wrong username or HTTP password ({})",
+      remote.username,
+      error_reason(body)
+    )))
+  }
+}
+
+/// Error of a request which the server answered with an unexpected HTTP status
+fn status_error(remote: &RemoteOpts, status: u32, body: &str) -> failure::Error {
+  failure::err_msg(format!(
+    "request to '{}' failed with HTTP status {}: {}",
+    remote.url,
+    status,
+    error_reason(body)
+  ))
+}
+
+/// Classify errors of requests which could not be completed by their libcurl error
+fn request_error(remote: &RemoteOpts, error: &curl::Error) -> failure::Error {
+  let reason = error.to_string();
+  let is_tls_error = error.is_peer_failed_verification()
+    || error.is_ssl_cacert()
+    || error.is_ssl_cacert_badfile()
+    || error.is_ssl_certproblem()
+    || error.is_ssl_connect_error()
+    || error.is_ssl_issuer_error();
+  let is_connect_error = error.is_couldnt_connect()
+    || error.is_couldnt_resolve_host()
+    || error.is_couldnt_resolve_proxy()
+    || error.is_operation_timedout();
+  if is_tls_error {
+    failure::err_msg(format!(
+      "TLS certificate of '{}' could not be verified: {}\n\
+       Use --ca-bundle with the CA certificate of the server or of a TLS intercepting proxy.",
+      remote.url, reason
+    ))
+  } else if is_connect_error {
+    failure::err_msg(format!("could not connect to '{}': {}", remote.url, reason))
+  } else {
+    failure::err_msg(format!("request to '{}' failed: {}", remote.url, reason))
+  }
+}
+
+fn error_reason(error: &str) -> &str {
+  error
+    .lines()
+    .map(|l| l.trim())
+    .filter(|l| !l.is_empty())
+    .last()
+    .unwrap_or("unknown error")
+}
@@ -2,6 +2,7 @@ use crate::util;
 use failure::ResultExt;
 use serde_derive::{Deserialize, Serialize};
 use std::collections::BTreeMap;
+use std::convert::TryFrom;
 use std::io::Write;
 use std::os::unix::fs::PermissionsExt;
 use std::path::PathBuf;
@@ -30,6 +31,23 @@ impl From<u64> for Verbosity {
 pub struct CliConfig {
   pub user: UserConfig,
   pub stdout: StandardStream,
+  pub format: OutputFormat,
+}
+
+/// Output formats for command results.
+#[derive(EnumString, Display, Debug, PartialEq, Eq, Copy, Clone)]
+#[strum(serialize_all = "snake_case")]
+pub enum OutputFormat {
+  /// Human-readable colored text.
+  Text,
+  /// Newline-delimited JSON records.
+  Json,
+  /// YAML documents.
+  Yaml,
+  /// Comma-separated values with a header line.
+  Csv,
+  /// Tab-separated values with a header line.
+  Tsv,
 }
 
 pub struct UserConfig {
@@ -41,6 +59,8 @@ pub struct UserConfig {
 #[serde(deny_unknown_fields, default)]
 pub struct UserSettings {
   default_remote: Option<String>,
+  /// Template to format each change of `change list` with.
+  pub list_format: Option<String>,
   pub remotes: BTreeMap<String, RemoteOpts>,
 }
 
@@ -105,10 +125,49 @@ impl UserSettings {
 pub struct RemoteOpts {
   pub url: String,
   pub username: String,
+  /// Plaintext HTTP password, only used with the `plaintext` credential source.
+  #[serde(skip_serializing_if = "String::is_empty")]
   pub http_password: String,
   pub http_auth: HttpAuthMethod,
   #[serde(skip_serializing_if = "util::is_false")]
   pub no_ssl_verify: bool,
+  /// CA certificates bundle to verify the server's certificate with.
+  /// Trusted in addition to the system's certificates, by both the REST API and git transports.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub ca_bundle: Option<String>,
+  /// HTTP(S) proxy URL. Defaults to the `HTTPS_PROXY` or `HTTP_PROXY` environment variables.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub proxy: Option<String>,
+  /// Comma-separated hosts not to use the proxy for. Defaults to the `NO_PROXY` environment variable.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub no_proxy: Option<String>,
+  /// Where to get the HTTP password from.
+  #[serde(skip_serializing_if = "CredentialSource::is_plaintext")]
+  pub credential: CredentialSource,
+  /// Environment variable holding the HTTP password, for the `env` credential source.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub password_env: Option<String>,
+  /// Command whose stdout is the HTTP password, for the `command` credential source.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub password_command: Option<String>,
+  /// Cookie file for the `cookie` HTTP authentication method.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub cookie_file: Option<String>,
+  /// Access token for the `bearer` HTTP authentication method.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub token: Option<String>,
+  /// Environment variable holding the access token, takes precedence over `token`.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub token_env: Option<String>,
+  /// Command whose stdout is the access token, takes precedence over `token_env` and `token`.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub token_command: Option<String>,
+  /// Transport of the commands which support running over SSH.
+  #[serde(skip_serializing_if = "Transport::is_rest")]
+  pub transport: Transport,
+  /// SSH connection for the `ssh` transport.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub ssh: Option<SshOpts>,
 }
 
 impl Default for RemoteOpts {
@@ -119,6 +178,112 @@ impl Default for RemoteOpts {
       http_password: Default::default(),
       http_auth: HttpAuthMethod::Basic,
       no_ssl_verify: false,
+      ca_bundle: None,
+      proxy: None,
+      no_proxy: None,
+      credential: CredentialSource::Plaintext,
+      password_env: None,
+      password_command: None,
+      cookie_file: None,
+      token: None,
+      token_env: None,
+      token_command: None,
+      transport: Transport::Rest,
+      ssh: None,
+    }
+  }
+}
+
+impl RemoteOpts {
+  /// Proxy to use for the remote: its `proxy` setting, else `HTTPS_PROXY` for https URLs or `HTTP_PROXY`.
+  pub fn effective_proxy(&self) -> Option<String> {
+    let env = |name: &str| {
+      std::env::var(name)
+        .or_else(|_| std::env::var(name.to_lowercase()))
+        .ok()
+        .filter(|v| !v.is_empty())
+    };
+    self.proxy.clone().or_else(|| match self.url.starts_with("https://") {
+      true => env("HTTPS_PROXY").or_else(|| env("HTTP_PROXY")),
+      false => env("HTTP_PROXY"),
+    })
+  }
+
+  /// Whether the host of the remote is excluded from the proxy by `effective_no_proxy`
+  pub fn bypasses_proxy(&self) -> bool {
+    let rest = self
+      .url
+      .find("://")
+      .map_or(self.url.as_str(), |index| &self.url[index + 3..]);
+    let host = rest.split(|c| c == '/' || c == ':').next().unwrap_or("");
+    self
+      .effective_no_proxy()
+      .map_or(false, |no_proxy| no_proxy_matches(&no_proxy, host))
+  }
+
+  /// Hosts not to use the proxy for: the `no_proxy` setting, else the `NO_PROXY` environment variable.
+  pub fn effective_no_proxy(&self) -> Option<String> {
+    self
+      .no_proxy
+      .clone()
+      .or_else(|| std::env::var("NO_PROXY").or_else(|_| std::env::var("no_proxy")).ok())
+  }
+}
+
+/// Check whether a host matches a comma-separated `NO_PROXY` list,
+/// whose entries match the host itself and its subdomains, `*` matching all hosts
+fn no_proxy_matches(no_proxy: &str, host: &str) -> bool {
+  let host = host.to_lowercase();
+  no_proxy
+    .split(',')
+    .map(|entry| entry.trim().trim_start_matches('.').to_lowercase())
+    .filter(|entry| !entry.is_empty())
+    .any(|entry| entry == "*" || host == entry || host.ends_with(&format!(".{}", entry)))
+}
+
+/// Transports to talk to a gerrit remote with.
+#[derive(EnumString, Display, Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum Transport {
+  /// The REST API over HTTP.
+  Rest,
+  /// The `gerrit` SSH command set.
+  Ssh,
+}
+
+impl Transport {
+  /// Function to check if transport is REST.
+  /// Used for serde 'skip_serializing_if' attributes.
+  pub fn is_rest(&self) -> bool {
+    *self == Transport::Rest
+  }
+}
+
+/// SSH connection options of a remote.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct SshOpts {
+  pub host: String,
+  pub port: u16,
+  /// User to login with, defaults to the remote's username.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub user: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub identity_file: Option<String>,
+  /// SSH client command, defaults to `ssh`. The `GER_SSH_COMMAND` environment variable takes precedence.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub command: Option<String>,
+}
+
+impl Default for SshOpts {
+  fn default() -> Self {
+    Self {
+      host: Default::default(),
+      port: 29418,
+      user: None,
+      identity_file: None,
+      command: None,
     }
   }
 }
@@ -132,6 +297,10 @@ pub enum HttpAuthMethod {
   Basic,
   /// Digest HTTP authentication scheme.
   Digest,
+  /// Cookies from the git cookie file, e.g. `~/.gitcookies`. Not supported by the REST API yet.
+  Cookie,
+  /// Access token sent in an `Authorization: Bearer` header. Not supported by the REST API yet.
+  Bearer,
 }
 
 impl From<gerlib::HttpAuthMethod> for HttpAuthMethod {
@@ -143,15 +312,49 @@ impl From<gerlib::HttpAuthMethod> for HttpAuthMethod {
   }
 }
 
-impl Into<gerlib::HttpAuthMethod> for HttpAuthMethod {
-  fn into(self) -> gerlib::HttpAuthMethod {
-    match self {
-      HttpAuthMethod::Basic => gerlib::HttpAuthMethod::Basic,
-      HttpAuthMethod::Digest => gerlib::HttpAuthMethod::Digest,
+/// Only the HTTP authentication schemes are supported by gerlib, cookies and bearer tokens
+/// are sent by the git transports alone.
+impl TryFrom<HttpAuthMethod> for gerlib::HttpAuthMethod {
+  type Error = failure::Error;
+
+  fn try_from(auth: HttpAuthMethod) -> Result<Self, Self::Error> {
+    match auth {
+      HttpAuthMethod::Basic => Ok(gerlib::HttpAuthMethod::Basic),
+      HttpAuthMethod::Digest => Ok(gerlib::HttpAuthMethod::Digest),
+      other => Err(failure::err_msg(format!(
+        "http_auth '{}' is only supported by git transports (push, checkout), \
+         gerlib cannot send it with REST API requests",
+        other
+      ))),
     }
   }
 }
 
+/// Sources of the HTTP password of a remote.
+#[derive(EnumString, Display, Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum CredentialSource {
+  /// The `http_password` saved in the configuration file.
+  Plaintext,
+  /// The git credential helper, through `git credential fill`.
+  GitCredential,
+  /// The `~/.netrc` file.
+  Netrc,
+  /// An environment variable, `GER_HTTP_PASSWORD` by default.
+  Env,
+  /// The output of `password_command`.
+  Command,
+}
+
+impl CredentialSource {
+  /// Function to check if source is plaintext.
+  /// Used for serde 'skip_serializing_if' attributes.
+  pub fn is_plaintext(&self) -> bool {
+    *self == CredentialSource::Plaintext
+  }
+}
+
 ////////////////////////////////////////////////////////////////////////////////////////////////////
 // /// TESTS
 // #[cfg(test)]
@@ -213,3 +416,18 @@ impl Into<gerlib::HttpAuthMethod> for HttpAuthMethod {
 //         assert_eq!(expected, actual);
 //     }
 // }
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  /// Expect no_proxy entries to match the host and its subdomains only
+  fn no_proxy_matches_hosts() {
+    assert!(no_proxy_matches("localhost, .example.com", "gerrit.example.com"));
+    assert!(no_proxy_matches("example.com", "EXAMPLE.com"));
+    assert!(no_proxy_matches("*", "gerrit.example.com"));
+    assert!(!no_proxy_matches("example.com", "badexample.com"));
+    assert!(!no_proxy_matches("", "example.com"));
+  }
+}
@@ -1,7 +1,10 @@
-use crate::config::CliConfig;
+use crate::config::{CliConfig, RemoteOpts, Transport};
+use crate::ssh::GerritSsh;
+use crate::util;
 use gerlib::GerritRestApi;
+use std::convert::TryFrom;
 
-pub fn get_remote_restapi_handler(config: &CliConfig, remote: Option<&str>) -> Result<GerritRestApi, failure::Error> {
+pub fn get_remote_opts<'a>(config: &'a CliConfig, remote: Option<&str>) -> Result<&'a RemoteOpts, failure::Error> {
   let remote = if let Some(this) = remote {
     this
   } else {
@@ -11,13 +14,60 @@ pub fn get_remote_restapi_handler(config: &CliConfig, remote: Option<&str>) -> R
     }
   };
 
-  let remote = match config.user.settings.remotes.get(remote) {
-    Some(r) => r,
-    None => return Err(failure::err_msg(format!("no such remote: {}", remote))),
-  };
+  match config.user.settings.remotes.get(remote) {
+    Some(r) => Ok(r),
+    None => Err(failure::err_msg(format!("no such remote: {}", remote))),
+  }
+}
+
+pub fn get_remote_restapi_handler(config: &CliConfig, remote: Option<&str>) -> Result<GerritRestApi, failure::Error> {
+  let remote = get_remote_opts(config, remote)?;
+  get_restapi_handler(remote)
+}
 
-  let handler = GerritRestApi::new(remote.url.parse()?, &remote.username, &remote.http_password)?
-    .http_auth(&remote.http_auth.clone().into())?
+/// Build the REST API handler of a remote.
+pub fn get_restapi_handler(remote: &RemoteOpts) -> Result<GerritRestApi, failure::Error> {
+  let http_auth = gerlib::HttpAuthMethod::try_from(remote.http_auth.clone())?;
+  let http_password = util::credential::http_password(remote)?;
+  set_curl_env(remote);
+  let handler = GerritRestApi::new(remote.url.parse()?, &remote.username, &http_password)?
+    .http_auth(&http_auth)?
     .ssl_verify(!remote.no_ssl_verify)?;
   Ok(handler)
 }
+
+/// Pass the proxy and CA bundle of a remote to the libcurl handles of the REST API.
+///
+/// gerlib has no options for them, but libcurl reads the proxy from the `http_proxy`, `https_proxy`
+/// and `no_proxy` environment variables, and on Linux the curl crate sets the CA bundle of its handles
+/// from `SSL_CERT_FILE`. The variables are set before the first request, so they apply to all handles.
+fn set_curl_env(remote: &RemoteOpts) {
+  if let Some(proxy) = &remote.proxy {
+    let name = if remote.url.starts_with("https://") {
+      "https_proxy"
+    } else {
+      "http_proxy"
+    };
+    std::env::set_var(name, proxy);
+  }
+  if let Some(no_proxy) = &remote.no_proxy {
+    std::env::set_var("no_proxy", no_proxy);
+  }
+  if let Some(ca_bundle) = &remote.ca_bundle {
+    std::env::set_var("SSL_CERT_FILE", ca_bundle);
+  }
+}
+
+/// Get the SSH handler of a remote if its transport is `ssh`, none otherwise.
+pub fn get_remote_ssh_handler(config: &CliConfig, remote: Option<&str>) -> Result<Option<GerritSsh>, failure::Error> {
+  let remote = get_remote_opts(config, remote)?;
+  if remote.transport != Transport::Ssh {
+    return Ok(None);
+  }
+  match &remote.ssh {
+    Some(ssh) => Ok(Some(GerritSsh::new(ssh, &remote.username))),
+    None => Err(failure::err_msg(
+      "remote has ssh transport but no ssh settings, see 'ger remote set --ssh-host'",
+    )),
+  }
+}
@@ -11,6 +11,8 @@ pub mod cli;
 pub mod commands;
 pub mod config;
 pub mod handler;
+pub mod output;
+pub mod ssh;
 pub mod ui;
 pub mod util;
 
@@ -0,0 +1,173 @@
+use crate::config::{CliConfig, OutputFormat};
+use serde::ser::{Serialize, SerializeMap, Serializer};
+use serde_json::Value;
+use std::io::Write;
+
+/// Record of named fields to be written out in a machine-readable format.
+///
+/// Fields keep the order in which they were added, so the output is stable.
+/// Records of the same kind are expected to always have the same fields, unset values being null,
+/// as these make up the columns of delimited outputs.
+#[derive(Default, Clone, Debug)]
+pub struct Record {
+  fields: Vec<(String, Value)>,
+  /// Fields which failed to serialize, reported when the record is written
+  errors: Vec<String>,
+}
+
+impl Record {
+  pub fn new() -> Self {
+    Default::default()
+  }
+
+  /// Append a field to the record
+  pub fn field<V: Serialize>(mut self, name: &str, value: V) -> Self {
+    let value = match serde_json::to_value(value) {
+      Ok(value) => value,
+      Err(e) => {
+        self.errors.push(format!("cannot serialize field '{}': {}", name, e));
+        Value::Null
+      }
+    };
+    self.fields.push((name.to_owned(), value));
+    self
+  }
+
+  /// Append all fields of another record
+  pub fn extend(&mut self, other: Record) {
+    self.fields.extend(other.fields);
+    self.errors.extend(other.errors);
+  }
+
+  /// Fail if any field of the record could not be serialized
+  fn check(&self) -> Result<(), failure::Error> {
+    match self.errors.first() {
+      Some(error) => Err(failure::err_msg(error.clone())),
+      None => Ok(()),
+    }
+  }
+
+  /// Get the value of a field, nested fields are accessed with dots, e.g. `owner.name`
+  pub fn get(&self, path: &str) -> Option<&Value> {
+    let mut parts = path.splitn(2, '.');
+    let name = parts.next()?;
+    let value = self.fields.iter().find(|(n, _)| n == name).map(|(_, v)| v)?;
+    match parts.next() {
+      Some(rest) => get_nested(value, rest),
+      None => Some(value),
+    }
+  }
+}
+
+impl Serialize for Record {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    let mut map = serializer.serialize_map(Some(self.fields.len()))?;
+    for (name, value) in &self.fields {
+      map.serialize_entry(name, value)?;
+    }
+    map.end()
+  }
+}
+
+fn get_nested<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+  // try the longest key first, as keys may contain dots themselves (e.g. file names)
+  if let Some(found) = value.get(path) {
+    return Some(found);
+  }
+  let index = path.find('.')?;
+  get_nested(value.get(&path[..index])?, &path[index + 1..])
+}
+
+/// Convert a value into a plain string, as used for template outputs
+pub fn value_to_string(value: &Value) -> String {
+  match value {
+    Value::Null => String::new(),
+    Value::String(s) => s.clone(),
+    Value::Array(array) => array.iter().map(value_to_string).collect::<Vec<_>>().join(","),
+    other => other.to_string(),
+  }
+}
+
+/// Write a single record in the configured output format
+pub fn write_record(config: &CliConfig, record: &Record) -> Result<(), failure::Error> {
+  match config.format {
+    OutputFormat::Yaml => {
+      record.check()?;
+      let yaml = serde_yaml::to_string(record)?;
+      writeln!(config.stdout.lock(), "{}", yaml.trim_end())?;
+      Ok(())
+    }
+    _ => write_records(config, std::slice::from_ref(record)),
+  }
+}
+
+/// Write a list of records in the configured output format.
+///
+/// JSON is written as newline-delimited records, YAML as a sequence and CSV/TSV with a header line
+/// of the record fields. Nested objects and arrays are written as a single JSON-encoded cell,
+/// so that the columns do not depend on the data. Nothing is written for the text format.
+pub fn write_records(config: &CliConfig, records: &[Record]) -> Result<(), failure::Error> {
+  for record in records {
+    record.check()?;
+  }
+  let mut stdout = config.stdout.lock();
+  match config.format {
+    OutputFormat::Text => {}
+    OutputFormat::Json | OutputFormat::Csv | OutputFormat::Tsv if records.is_empty() => {}
+    OutputFormat::Json => {
+      for record in records {
+        writeln!(stdout, "{}", serde_json::to_string(record)?)?;
+      }
+    }
+    OutputFormat::Yaml => {
+      let yaml = serde_yaml::to_string(records)?;
+      writeln!(stdout, "{}", yaml.trim_end())?;
+    }
+    OutputFormat::Csv | OutputFormat::Tsv => {
+      let delimiter = if config.format == OutputFormat::Csv { ',' } else { '\t' };
+      let mut header: Vec<&str> = Vec::new();
+      for record in records {
+        for (name, _) in &record.fields {
+          if !header.contains(&name.as_str()) {
+            header.push(name.as_str());
+          }
+        }
+      }
+      let line: Vec<String> = header.iter().map(|name| escape_cell(name, delimiter)).collect();
+      writeln!(stdout, "{}", line.join(&delimiter.to_string()))?;
+      for record in records {
+        let mut line = Vec::with_capacity(header.len());
+        for name in &header {
+          let cell = match record.fields.iter().find(|(n, _)| n == name) {
+            Some((_, value)) => cell_to_string(value)?,
+            None => String::new(),
+          };
+          line.push(escape_cell(&cell, delimiter));
+        }
+        writeln!(stdout, "{}", line.join(&delimiter.to_string()))?;
+      }
+    }
+  }
+  Ok(())
+}
+
+/// Convert a value into a cell of delimiter-separated output, objects and arrays being JSON-encoded
+fn cell_to_string(value: &Value) -> Result<String, failure::Error> {
+  match value {
+    Value::Object(_) | Value::Array(_) => Ok(serde_json::to_string(value)?),
+    other => Ok(value_to_string(other)),
+  }
+}
+
+/// Escape a cell of delimiter-separated output.
+/// CSV cells are quoted when needed, TSV cells have tabs and newlines replaced by spaces.
+fn escape_cell(cell: &str, delimiter: char) -> String {
+  if delimiter == '\t' {
+    return cell.replace(|c| c == '\t' || c == '\n' || c == '\r', " ");
+  }
+  if cell.contains(|c| c == ',' || c == '"' || c == '\n' || c == '\r') {
+    format!("\"{}\"", cell.replace('"', "\"\""))
+  } else {
+    cell.to_owned()
+  }
+}
@@ -0,0 +1,363 @@
+//! Gerrit SSH command set transport, i.e. `ssh -p 29418 host gerrit <command>`.
+//!
+//! Output of `gerrit query --format=JSON` is converted into the REST API's `ChangeInfo`
+//! so that commands can display changes the same way for both transports.
+//! Account ids are not part of that output, see `account_info`.
+//!
+//! `test/fake-ssh` and `test/fake-gerrit` stand in for the ssh client and the server.
+
+use crate::config::SshOpts;
+use chrono::{TimeZone, Utc};
+use failure::ResultExt;
+use gerlib::changes::ChangeInfo;
+use log::debug;
+use serde_json::{json, Value};
+use std::process::{Command, Stdio};
+
+pub struct GerritSsh {
+  opts: SshOpts,
+  user: String,
+}
+
+impl GerritSsh {
+  /// Create a handler for the SSH connection, `username` being the default user.
+  pub fn new(opts: &SshOpts, username: &str) -> Self {
+    Self {
+      opts: opts.clone(),
+      user: opts.user.clone().unwrap_or_else(|| username.to_owned()),
+    }
+  }
+
+  /// Run a gerrit command and return its stdout
+  fn run(&self, args: &[&str]) -> Result<String, failure::Error> {
+    let ssh = std::env::var("GER_SSH_COMMAND")
+      .ok()
+      .or_else(|| self.opts.command.clone())
+      .unwrap_or_else(|| "ssh".to_owned());
+    let mut ssh_args = vec!["-p".to_owned(), self.opts.port.to_string()];
+    if let Some(identity_file) = &self.opts.identity_file {
+      ssh_args.push("-i".to_owned());
+      ssh_args.push(identity_file.clone());
+    }
+    if !self.user.is_empty() {
+      ssh_args.push("-l".to_owned());
+      ssh_args.push(self.user.clone());
+    }
+    ssh_args.push(self.opts.host.clone());
+    ssh_args.push("gerrit".to_owned());
+    // the remote side splits the command line again, so arguments are quoted
+    ssh_args.extend(args.iter().map(|arg| quote(arg)));
+    debug!("ssh command: {} {}", ssh, ssh_args.join(" "));
+
+    let output = Command::new("sh")
+      .arg("-c")
+      .arg(format!("{} \"$@\"", ssh))
+      .arg(&ssh)
+      .args(&ssh_args)
+      .stdin(Stdio::null())
+      .output()
+      .with_context(|_| format!("failed to run ssh command: {}", ssh))?;
+    if !output.status.success() {
+      let stderr = String::from_utf8_lossy(&output.stderr);
+      return Err(failure::err_msg(format!(
+        "gerrit {} failed over ssh: {}",
+        args.first().unwrap_or(&""),
+        stderr.trim()
+      )));
+    }
+    Ok(String::from_utf8(output.stdout)?)
+  }
+
+  /// Query changes, as `gerrit query --format=JSON --current-patch-set`
+  pub fn query_changes(
+    &self, query: &str, limit: Option<u32>, start: Option<u32>,
+  ) -> Result<Vec<ChangeInfo>, failure::Error> {
+    let mut args = vec!["query", "--format=JSON", "--current-patch-set"];
+    let start = start.map(|s| s.to_string());
+    if let Some(start) = &start {
+      args.push("--start");
+      args.push(start);
+    }
+    let query = match limit {
+      Some(limit) => format!("{} limit:{}", query, limit),
+      None => query.to_owned(),
+    };
+    args.push(&query);
+
+    let output = self.run(&args)?;
+    let mut changes = Vec::new();
+    for line in output.lines().filter(|l| !l.trim().is_empty()) {
+      let value: Value = serde_json::from_str(line)?;
+      match value["type"].as_str() {
+        Some("stats") => {}
+        Some("error") => return Err(failure::err_msg(format!("gerrit query: {}", value["message"]))),
+        _ => changes.push(change_info(&value)?),
+      }
+    }
+    Ok(changes)
+  }
+
+  /// Get a change, including its current patch set
+  pub fn get_change(&self, change_id: &str) -> Result<ChangeInfo, failure::Error> {
+    self
+      .query_changes(&change_query(change_id)?, Some(1), None)?
+      .pop()
+      .ok_or_else(|| failure::err_msg(format!("no such change: {}", change_id)))
+  }
+
+  /// Review the current patch set of a change, as `gerrit review`.
+  ///
+  /// `labels` are given as `Label=Vote`, `notify` as the REST API's `notify` values.
+  pub fn review(
+    &self, change_id: &str, message: Option<&str>, labels: &[&str], notify: Option<&str>, abandon: bool,
+  ) -> Result<(), failure::Error> {
+    let patch_set = self.current_patch_set(change_id)?;
+    let mut args = vec!["review".to_owned(), patch_set];
+    if let Some(message) = message {
+      args.push("--message".to_owned());
+      args.push(message.to_owned());
+    }
+    for label in labels {
+      args.push("--label".to_owned());
+      args.push((*label).to_owned());
+    }
+    if let Some(notify) = notify {
+      args.push("--notify".to_owned());
+      args.push(notify.to_uppercase());
+    }
+    if abandon {
+      args.push("--abandon".to_owned());
+    }
+    let args: Vec<&str> = args.iter().map(|a| a.as_str()).collect();
+    self.run(&args)?;
+    Ok(())
+  }
+
+  /// Set the topic of a change, as `gerrit set-topic`. An empty topic deletes it.
+  pub fn set_topic(&self, change_id: &str, topic: &str) -> Result<(), failure::Error> {
+    self.run(&["set-topic", change_id, "--topic", topic])?;
+    Ok(())
+  }
+
+  /// Current patch set of a change as `<number>,<patch set>`, which `gerrit review` takes
+  fn current_patch_set(&self, change_id: &str) -> Result<String, failure::Error> {
+    let output = self.run(&[
+      "query",
+      "--format=JSON",
+      "--current-patch-set",
+      &change_query(change_id)?,
+    ])?;
+    let value: Value = match output.lines().next() {
+      Some(line) => serde_json::from_str(line)?,
+      None => Value::Null,
+    };
+    match (number(&value["number"]), number(&value["currentPatchSet"]["number"])) {
+      (Some(change), Some(patch_set)) => Ok(format!("{},{}", change, patch_set)),
+      _ => Err(failure::err_msg(format!("no such change: {}", change_id))),
+    }
+  }
+}
+
+/// Quote an argument for the command line parser of the gerrit SSH daemon
+fn quote(arg: &str) -> String {
+  if !arg.is_empty()
+    && arg
+      .chars()
+      .all(|c| c.is_ascii_alphanumeric() || "-_=+:,./@~".contains(c))
+  {
+    return arg.to_owned();
+  }
+  format!("'{}'", arg.replace('\'', "'\\''"))
+}
+
+/// Query for a change identifier: numeric id, Change-Id, `project~branch~Change-Id` or commit SHA-1
+fn change_query(change_id: &str) -> Result<String, failure::Error> {
+  let parts: Vec<&str> = change_id.split('~').collect();
+  if let [project, branch, id] = parts[..] {
+    if project.is_empty() || branch.is_empty() || !is_change_id(id) {
+      return Err(failure::err_msg(format!(
+        "invalid change identifier '{}', expected project~branch~Change-Id",
+        change_id
+      )));
+    }
+    let project = project.replace("%2F", "/").replace("%2f", "/");
+    return Ok(format!("project:{} branch:{} change:{}", project, branch, id));
+  }
+  if parts.len() > 1 {
+    return Err(failure::err_msg(format!("invalid change identifier '{}'", change_id)));
+  }
+  if change_id.chars().all(|c| c.is_ascii_digit()) || is_change_id(change_id) {
+    Ok(format!("change:{}", change_id))
+  } else {
+    Ok(format!("commit:{}", change_id))
+  }
+}
+
+/// Whether an identifier has the shape of a full or abbreviated Change-Id, `I` and up to 40 hex digits
+fn is_change_id(id: &str) -> bool {
+  match id.strip_prefix('I') {
+    Some(hex) => !hex.is_empty() && hex.len() <= 40 && hex.chars().all(|c| c.is_ascii_hexdigit()),
+    None => false,
+  }
+}
+
+/// Numbers are strings in the output of older gerrit versions
+fn number(value: &Value) -> Option<u64> {
+  value.as_u64().or_else(|| value.as_str().and_then(|s| s.parse().ok()))
+}
+
+/// Convert seconds since the epoch to the REST API's timestamp format
+fn timestamp(value: &Value) -> Value {
+  match value.as_i64() {
+    Some(secs) => Value::String(Utc.timestamp(secs, 0).format("%Y-%m-%d %H:%M:%S%.9f").to_string()),
+    None => Value::Null,
+  }
+}
+
+/// Convert an account of the SSH output to the REST API's `AccountInfo`.
+///
+/// Account ids are not part of the SSH output, so `_account_id` is always 0
+/// and must not be used to tell accounts of changes queried over SSH apart.
+fn account_info(value: &Value) -> Value {
+  json!({
+    "_account_id": 0,
+    "name": value["name"],
+    "email": value["email"],
+    "username": value["username"],
+  })
+}
+
+/// Convert a change of the `gerrit query --format=JSON` output to the REST API's `ChangeInfo`
+fn change_info(value: &Value) -> Result<ChangeInfo, failure::Error> {
+  let patch_set = &value["currentPatchSet"];
+  let status = match value["status"].as_str() {
+    Some("NEW") | None => "NEW",
+    Some(status) => status,
+  };
+  let mut change = json!({
+    "id": format!(
+      "{}~{}~{}",
+      value["project"].as_str().unwrap_or(""),
+      value["branch"].as_str().unwrap_or(""),
+      value["id"].as_str().unwrap_or("")
+    ),
+    "project": value["project"],
+    "branch": value["branch"],
+    "topic": value["topic"],
+    "hashtags": value.get("hashtags").cloned().unwrap_or_else(|| json!([])),
+    "change_id": value["id"],
+    "subject": value["subject"],
+    "status": status,
+    "created": timestamp(&value["createdOn"]),
+    "updated": timestamp(&value["lastUpdated"]),
+    "insertions": patch_set["sizeInsertions"].as_i64().unwrap_or(0),
+    "deletions": patch_set["sizeDeletions"].as_i64().unwrap_or(0).abs(),
+    "_number": number(&value["number"]).unwrap_or(0),
+    "owner": account_info(&value["owner"]),
+    "work_in_progress": value["wip"].as_bool().unwrap_or(false),
+    "is_private": value["private"].as_bool().unwrap_or(false),
+  });
+  if let Some(revision) = patch_set["revision"].as_str() {
+    change["current_revision"] = json!(revision);
+    change["revisions"] = json!({
+      revision: {
+        "_number": number(&patch_set["number"]).unwrap_or(0),
+        "ref": patch_set["ref"],
+        "created": timestamp(&patch_set["createdOn"]),
+        "uploader": account_info(&patch_set["uploader"]),
+      }
+    });
+  }
+  Ok(serde_json::from_value(change).context("unexpected change in gerrit query output")?)
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  /// SSH handler that runs the commands with `test/fake-gerrit` through `test/fake-ssh`
+  fn fake_ssh() -> GerritSsh {
+    let opts = SshOpts {
+      host: "gerrit.example.com".to_owned(),
+      port: 29418,
+      user: Some("gerrit".to_owned()),
+      identity_file: None,
+      command: Some(concat!(env!("CARGO_MANIFEST_DIR"), "/test/fake-ssh").to_owned()),
+    };
+    GerritSsh::new(&opts, "jane")
+  }
+
+  #[test]
+  /// Expect the recorded query output to be converted to changes, skipping the stats line
+  fn query_changes_fake_gerrit() {
+    let changes = fake_ssh()
+      .query_changes("status:open project:ger", Some(2), Some(0))
+      .unwrap();
+    assert_eq!(2, changes.len());
+    assert_eq!(15813, changes[0].number);
+    assert_eq!("Add SSH transport", changes[0].subject);
+    assert_eq!(Some("ssh".to_owned()), changes[0].topic);
+    assert_eq!(Some("Jane Doe".to_owned()), changes[0].owner.name);
+    assert_eq!(15790, changes[1].number);
+    assert_eq!("stable", changes[1].branch);
+  }
+
+  #[test]
+  /// Expect only identifiers shaped as Change-Ids to be queried as such
+  fn change_query_kinds() {
+    let change_id = "I8473b95934b5732ac55d26311a706c9c2bde9940";
+    assert_eq!("change:15813", change_query("15813").unwrap());
+    assert_eq!(format!("change:{}", change_id), change_query(change_id).unwrap());
+    assert_eq!("change:Ic0ff33", change_query("Ic0ff33").unwrap());
+    assert_eq!("commit:d81b32ef", change_query("d81b32ef").unwrap());
+    assert_eq!("commit:Invalid", change_query("Invalid").unwrap());
+    assert_eq!(
+      format!("project:tools/ger branch:master change:{}", change_id),
+      change_query(&format!("tools%2Fger~master~{}", change_id)).unwrap()
+    );
+    assert!(change_query("ger~master~15813").is_err());
+    assert!(change_query("ger~15813").is_err());
+  }
+
+  #[test]
+  /// Expect a change of the query output to be converted with its current patch set
+  fn change_info_current_patch_set() {
+    let line = include_str!("../test/fixtures/query.json").lines().next().unwrap();
+    let change = change_info(&serde_json::from_str(line).unwrap()).unwrap();
+    assert_eq!(15813, change.number);
+    assert_eq!("ger", change.project);
+    assert_eq!("master", change.branch);
+    assert_eq!("ger~master~I8473b95934b5732ac55d26311a706c9c2bde9940", change.id);
+    assert_eq!(120, change.insertions);
+    assert_eq!(7, change.deletions);
+    assert!(change.work_in_progress);
+    assert!(!change.is_private);
+    let revision = "d81b32ef6bd3a4ac8e4c0ef5c03f30a29b2e2a4b";
+    assert_eq!(Some(revision.to_owned()), change.current_revision);
+    assert_eq!(3, change.revisions.unwrap()[revision]._number);
+  }
+
+  #[test]
+  /// Expect change numbers given as strings by older gerrit versions to be converted
+  fn change_info_string_numbers() {
+    let line = include_str!("../test/fixtures/query.json").lines().nth(1).unwrap();
+    let change = change_info(&serde_json::from_str(line).unwrap()).unwrap();
+    assert_eq!(15790, change.number);
+    assert_eq!(
+      1,
+      change.revisions.unwrap()["0123456789abcdef0123456789abcdef01234567"]._number
+    );
+  }
+
+  #[test]
+  /// Expect arguments to be quoted only if the gerrit command line parser would split them
+  fn quote_arguments() {
+    assert_eq!("--label", quote("--label"));
+    assert_eq!("Code-Review=+2", quote("Code-Review=+2"));
+    assert_eq!("15813,3", quote("15813,3"));
+    assert_eq!("'Looks good'", quote("Looks good"));
+    assert_eq!("''", quote(""));
+    assert_eq!("'it'\\''s'", quote("it's"));
+    assert_eq!("'$HOME'", quote("$HOME"));
+  }
+}
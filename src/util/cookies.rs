@@ -0,0 +1,205 @@
+use crate::config::RemoteOpts;
+use std::path::PathBuf;
+
+/// A cookie of a Netscape cookie file, the format of git's `http.cookiefile`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cookie {
+  pub domain: String,
+  pub include_subdomains: bool,
+  pub path: String,
+  pub secure: bool,
+  /// Expiration as seconds since the epoch, 0 for session cookies
+  pub expires: u64,
+  pub name: String,
+  pub value: String,
+}
+
+impl Cookie {
+  /// Check whether the cookie is to be sent to a host and path
+  fn matches(&self, host: &str, path: &str, https: bool, now: u64) -> bool {
+    let domain = self.domain.trim_start_matches('.');
+    let domain_matches = host.eq_ignore_ascii_case(domain)
+      || ((self.include_subdomains || self.domain.starts_with('.'))
+        && host.to_lowercase().ends_with(&format!(".{}", domain.to_lowercase())));
+    domain_matches && self.path_matches(path) && (https || !self.secure) && (self.expires == 0 || self.expires > now)
+  }
+
+  /// Check whether the cookie path matches a request path on path segments, i.e. `/a` matches `/a/b` but not `/ab`
+  fn path_matches(&self, path: &str) -> bool {
+    path == self.path
+      || (path.starts_with(&self.path) && (self.path.ends_with('/') || path[self.path.len()..].starts_with('/')))
+  }
+}
+
+/// Cookie file of a remote: its `cookie_file` setting, else git's `http.cookiefile`, else `~/.gitcookies`
+pub fn cookie_file(remote: &RemoteOpts) -> Result<PathBuf, failure::Error> {
+  if let Some(file) = &remote.cookie_file {
+    return expand_home(file);
+  }
+  match git2::Config::open_default().and_then(|config| config.get_path("http.cookiefile")) {
+    Ok(path) => Ok(path),
+    Err(_) => Ok(super::home_dir()?.join(".gitcookies")),
+  }
+}
+
+fn expand_home(path: &str) -> Result<PathBuf, failure::Error> {
+  match path.strip_prefix("~/") {
+    Some(rest) => Ok(super::home_dir()?.join(rest)),
+    None => Ok(path.into()),
+  }
+}
+
+/// Parse the contents of a Netscape cookie file, skipping comments and malformed lines
+pub fn parse(contents: &str) -> Vec<Cookie> {
+  contents
+    .lines()
+    .filter_map(|line| {
+      let line = line.strip_prefix("#HttpOnly_").unwrap_or(line);
+      if line.starts_with('#') {
+        return None;
+      }
+      let fields: Vec<&str> = line.split('\t').collect();
+      if fields.len() != 7 {
+        return None;
+      }
+      Some(Cookie {
+        domain: fields[0].to_owned(),
+        include_subdomains: fields[1].eq_ignore_ascii_case("TRUE"),
+        path: fields[2].to_owned(),
+        secure: fields[3].eq_ignore_ascii_case("TRUE"),
+        expires: fields[4].parse().unwrap_or(0),
+        name: fields[5].to_owned(),
+        value: fields[6].to_owned(),
+      })
+    })
+    .collect()
+}
+
+/// Find the cookies of a remote's URL in its cookie file.
+///
+/// Cookies of the most specific domain come first, and only the first cookie of each name is kept.
+pub fn find_cookies(remote: &RemoteOpts) -> Result<Vec<Cookie>, failure::Error> {
+  let path = cookie_file(remote)?;
+  let contents = std::fs::read_to_string(&path)
+    .map_err(|e| failure::err_msg(format!("cannot read cookie file {}: {}", path.display(), e)))?;
+  let now = std::time::SystemTime::now()
+    .duration_since(std::time::UNIX_EPOCH)
+    .map(|d| d.as_secs())
+    .unwrap_or(0);
+
+  let cookies = url_cookies(parse(&contents), &remote.url, now);
+  if cookies.is_empty() {
+    return Err(failure::err_msg(format!(
+      "no cookie for {} in {}",
+      remote.url,
+      path.display()
+    )));
+  }
+  Ok(cookies)
+}
+
+/// Select the cookies to send to a URL, most specific domain first and only the first cookie of each name
+fn url_cookies(cookies: Vec<Cookie>, url: &str, now: u64) -> Vec<Cookie> {
+  let https = url.starts_with("https://");
+  let rest = url.find("://").map_or(url, |index| &url[index + 3..]);
+  let (authority, url_path) = match rest.find('/') {
+    Some(index) => (&rest[..index], &rest[index..]),
+    None => (rest, "/"),
+  };
+  let host = authority.rsplit('@').next().unwrap().split(':').next().unwrap();
+
+  let mut cookies: Vec<Cookie> = cookies
+    .into_iter()
+    .filter(|cookie| cookie.matches(host, url_path, https, now))
+    .collect();
+  cookies.sort_by_key(|cookie| std::cmp::Reverse(cookie.domain.trim_start_matches('.').len()));
+  let mut names = std::collections::HashSet::new();
+  cookies.retain(|cookie| names.insert(cookie.name.clone()));
+  cookies
+}
+
+/// Value of the `Cookie` HTTP header sending the given cookies
+pub fn cookie_header(cookies: &[Cookie]) -> String {
+  cookies
+    .iter()
+    .map(|cookie| format!("{}={}", cookie.name, cookie.value))
+    .collect::<Vec<_>>()
+    .join("; ")
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  const COOKIES: &str = "\
+# Netscape HTTP Cookie File
+.example.com\tTRUE\t/\tTRUE\t2147483647\to\tgit-jane.example.com=1/abc
+#HttpOnly_review.example.com\tFALSE\t/\tTRUE\t0\to\tgit-jane.review=1/def
+review.example.com\tFALSE\t/a\tFALSE\t2147483647\tpath\ta
+review.example.com\tFALSE\t/\tFALSE\t1000\texpired\tb
+malformed line
+";
+
+  fn cookie(domain: &str, path: &str) -> Cookie {
+    Cookie {
+      domain: domain.to_owned(),
+      include_subdomains: false,
+      path: path.to_owned(),
+      secure: false,
+      expires: 0,
+      name: "o".to_owned(),
+      value: "v".to_owned(),
+    }
+  }
+
+  #[test]
+  /// Expect comments and malformed lines to be skipped, and HttpOnly cookies to be kept
+  fn parse_cookie_file() {
+    let cookies = parse(COOKIES);
+    assert_eq!(4, cookies.len());
+    assert_eq!(".example.com", cookies[0].domain);
+    assert!(cookies[0].include_subdomains);
+    assert!(cookies[0].secure);
+    assert_eq!("git-jane.example.com=1/abc", cookies[0].value);
+    assert_eq!("review.example.com", cookies[1].domain);
+    assert_eq!(0, cookies[1].expires);
+    assert_eq!("/a", cookies[2].path);
+  }
+
+  #[test]
+  /// Expect cookie paths to match on path segments only
+  fn cookie_path_segments() {
+    let a = cookie("example.com", "/a");
+    assert!(a.matches("example.com", "/a", false, 0));
+    assert!(a.matches("example.com", "/a/b", false, 0));
+    assert!(!a.matches("example.com", "/ab", false, 0));
+    assert!(!a.matches("example.com", "/", false, 0));
+    assert!(cookie("example.com", "/a/").matches("example.com", "/a/b", false, 0));
+  }
+
+  #[test]
+  /// Expect domains to match subdomains only if the cookie allows it
+  fn cookie_domains() {
+    assert!(cookie("example.com", "/").matches("EXAMPLE.com", "/", false, 0));
+    assert!(!cookie("example.com", "/").matches("review.example.com", "/", false, 0));
+    assert!(cookie(".example.com", "/").matches("review.example.com", "/", false, 0));
+    assert!(!cookie(".example.com", "/").matches("badexample.com", "/", false, 0));
+  }
+
+  #[test]
+  /// Expect secure and expired cookies to be left out where they do not apply
+  fn url_cookies_secure_expired() {
+    let cookies = url_cookies(parse(COOKIES), "http://review.example.com/a/b", 2000);
+    let names: Vec<&str> = cookies.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(vec!["path"], names);
+  }
+
+  #[test]
+  /// Expect the cookie of the most specific domain to win over others of the same name
+  fn url_cookies_most_specific() {
+    let cookies = url_cookies(parse(COOKIES), "https://jane@review.example.com:8443/r", 2000);
+    assert_eq!(1, cookies.len());
+    assert_eq!("git-jane.review=1/def", cookies[0].value);
+    assert_eq!("o=git-jane.review=1/def", cookie_header(&cookies));
+  }
+}
@@ -0,0 +1,223 @@
+use crate::config::{CredentialSource, RemoteOpts};
+use failure::ResultExt;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Default environment variable of the `env` credential source
+pub static DEFAULT_PASSWORD_ENV: &str = "GER_HTTP_PASSWORD";
+
+/// Get the HTTP password of a remote from its configured credential source
+pub fn http_password(remote: &RemoteOpts) -> Result<String, failure::Error> {
+  match remote.credential {
+    CredentialSource::Plaintext => Ok(remote.http_password.clone()),
+    CredentialSource::GitCredential => git_credential_fill(&remote.url, &remote.username)?
+      .ok_or_else(|| failure::err_msg(format!("git credential helper has no password for {}", remote.url))),
+    CredentialSource::Netrc => netrc_password(&remote.url, &remote.username)?
+      .ok_or_else(|| failure::err_msg(format!("no password for {} in netrc file", remote.url))),
+    CredentialSource::Env => {
+      let var = remote.password_env.as_deref().unwrap_or(DEFAULT_PASSWORD_ENV);
+      std::env::var(var).map_err(|_| failure::err_msg(format!("environment variable {} is not set", var)))
+    }
+    CredentialSource::Command => {
+      let command = remote
+        .password_command
+        .as_ref()
+        .ok_or_else(|| failure::err_msg("credential source 'command' requires password_command"))?;
+      password_from_command(command)
+    }
+  }
+}
+
+/// Get the access token of a remote with bearer authentication.
+///
+/// `token_command` takes precedence over `token_env`, which takes precedence over `token`.
+/// Without any of them, the token is taken from the git credential helper if that is the credential source.
+pub fn bearer_token(remote: &RemoteOpts) -> Result<String, failure::Error> {
+  if let Some(command) = &remote.token_command {
+    return password_from_command(command);
+  }
+  if let Some(var) = &remote.token_env {
+    return std::env::var(var).map_err(|_| failure::err_msg(format!("environment variable {} is not set", var)));
+  }
+  if let Some(token) = &remote.token {
+    return Ok(token.clone());
+  }
+  match remote.credential {
+    CredentialSource::GitCredential => git_credential_fill(&remote.url, &remote.username)?
+      .ok_or_else(|| failure::err_msg(format!("git credential helper has no token for {}", remote.url))),
+    _ => Err(failure::err_msg(
+      "bearer authentication requires token, token_env, token_command or the git_credential credential source",
+    )),
+  }
+}
+
+/// Run a shell command and take the first line of its stdout as password
+fn password_from_command(command: &str) -> Result<String, failure::Error> {
+  let output = Command::new("sh")
+    .arg("-c")
+    .arg(command)
+    .stderr(Stdio::inherit())
+    .output()
+    .with_context(|_| format!("failed to run command: {}", command))?;
+  if !output.status.success() {
+    return Err(failure::err_msg(format!(
+      "command '{}' exited with {}",
+      command, output.status
+    )));
+  }
+  let stdout = String::from_utf8(output.stdout)?;
+  Ok(stdout.lines().next().unwrap_or("").to_owned())
+}
+
+/// Build the attributes of the git credential protocol for a URL
+fn credential_description(url: &str, username: &str) -> String {
+  let (protocol, rest) = match url.find("://") {
+    Some(index) => (&url[..index], &url[index + 3..]),
+    None => ("https", url),
+  };
+  let host = rest.split('/').next().unwrap_or("");
+  let mut description = format!("protocol={}\nhost={}\n", protocol, host);
+  if !username.is_empty() {
+    description.push_str(&format!("username={}\n", username));
+  }
+  description
+}
+
+/// Run `git credential <action>` feeding it `input` and return its output
+fn git_credential(action: &str, input: &str) -> Result<String, failure::Error> {
+  let mut child = Command::new("git")
+    .arg("credential")
+    .arg(action)
+    .stdin(Stdio::piped())
+    .stdout(Stdio::piped())
+    .spawn()
+    .context("failed to run git credential")?;
+  child
+    .stdin
+    .take()
+    .unwrap()
+    .write_all(format!("{}\n", input).as_bytes())?;
+  let output = child.wait_with_output()?;
+  if !output.status.success() {
+    return Err(failure::err_msg(format!(
+      "git credential {} exited with {}",
+      action, output.status
+    )));
+  }
+  Ok(String::from_utf8(output.stdout)?)
+}
+
+/// Ask the git credential helper for the password of a user at a URL
+pub fn git_credential_fill(url: &str, username: &str) -> Result<Option<String>, failure::Error> {
+  let output = git_credential("fill", &credential_description(url, username))?;
+  Ok(
+    output
+      .lines()
+      .find(|l| l.starts_with("password="))
+      .map(|l| l["password=".len()..].to_owned()),
+  )
+}
+
+/// Store the password of a user at a URL through the git credential helper
+pub fn git_credential_approve(url: &str, username: &str, password: &str) -> Result<(), failure::Error> {
+  let input = format!("{}password={}\n", credential_description(url, username), password);
+  git_credential("approve", &input)?;
+  Ok(())
+}
+
+/// Look up the password of a user at a URL in `$NETRC` or `~/.netrc`.
+///
+/// The first `machine` entry of the host with a matching `login` (if any) wins, else the `default` entry.
+pub fn netrc_password(url: &str, username: &str) -> Result<Option<String>, failure::Error> {
+  let path = match std::env::var_os("NETRC") {
+    Some(path) => path.into(),
+    None => super::home_dir()?.join(".netrc"),
+  };
+  let contents = match std::fs::read_to_string(&path) {
+    Ok(contents) => contents,
+    Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+    Err(e) => return Err(failure::err_msg(format!("cannot read {}: {}", path.display(), e))),
+  };
+  Ok(parse_netrc(&contents, url, username))
+}
+
+/// Find the password of a user at a URL in the contents of a netrc file
+fn parse_netrc(contents: &str, url: &str, username: &str) -> Option<String> {
+  let rest = url.find("://").map_or(url, |index| &url[index + 3..]);
+  let host = rest.split(|c| c == '/' || c == ':').next().unwrap_or("");
+
+  // (machine, login, password) entries, `None` machine being the default entry
+  let mut entries: Vec<(Option<String>, Option<String>, Option<String>)> = Vec::new();
+  let mut tokens = contents.split_whitespace();
+  while let Some(token) = tokens.next() {
+    match token {
+      "machine" => entries.push((tokens.next().map(|t| t.to_owned()), None, None)),
+      "default" => entries.push((None, None, None)),
+      "login" => {
+        if let Some(entry) = entries.last_mut() {
+          entry.1 = tokens.next().map(|t| t.to_owned());
+        }
+      }
+      "password" => {
+        if let Some(entry) = entries.last_mut() {
+          entry.2 = tokens.next().map(|t| t.to_owned());
+        }
+      }
+      _ => {}
+    }
+  }
+
+  let login_matches = |login: &Option<String>| username.is_empty() || login.as_deref().map_or(true, |l| l == username);
+  let entry = entries
+    .iter()
+    .find(|(machine, login, _)| machine.as_deref() == Some(host) && login_matches(login))
+    .or_else(|| {
+      entries
+        .iter()
+        .find(|(machine, login, _)| machine.is_none() && login_matches(login))
+    });
+  entry.and_then(|(_, _, password)| password.clone())
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  const NETRC: &str = "machine review.example.com login alice password secret1
+machine review.example.com
+  login bob
+  password secret2
+default login anonymous password guest
+";
+
+  #[test]
+  /// Expect the entry of the host with the matching login
+  fn netrc_matching_login() {
+    let url = "https://review.example.com/a/project";
+    assert_eq!(parse_netrc(NETRC, url, "alice").as_deref(), Some("secret1"));
+    assert_eq!(parse_netrc(NETRC, url, "bob").as_deref(), Some("secret2"));
+  }
+
+  #[test]
+  /// Expect the first entry of the host without a username
+  fn netrc_any_login() {
+    assert_eq!(
+      parse_netrc(NETRC, "https://review.example.com:8443", "").as_deref(),
+      Some("secret1")
+    );
+  }
+
+  #[test]
+  /// Expect the default entry for other hosts or unknown logins
+  fn netrc_default() {
+    assert_eq!(
+      parse_netrc(NETRC, "https://other.example.com", "anonymous").as_deref(),
+      Some("guest")
+    );
+    assert_eq!(parse_netrc(NETRC, "https://review.example.com", "carol"), None);
+    assert_eq!(
+      parse_netrc("machine review.example.com login alice", "review.example.com", "alice"),
+      None
+    );
+  }
+}
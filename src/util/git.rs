@@ -0,0 +1,253 @@
+use super::{cookies, credential};
+use crate::config::{HttpAuthMethod, RemoteOpts};
+use git2::{Cred, ProxyOptions, RemoteCallbacks, Repository, StatusOptions};
+use std::cell::Cell;
+use std::ffi::CString;
+use std::os::raw::{c_char, c_int};
+
+/// Build git remote callbacks which authenticate with the credentials of a gerrit remote.
+///
+/// HTTP transports use the remote's username and HTTP password from its credential source,
+/// SSH transports use the ssh-agent. Remotes with `cookie` or `bearer` auth authenticate
+/// through `remote_headers` instead, so no password is offered for them.
+/// Credentials are offered only once, as libgit2 asks again after each rejection.
+pub fn remote_callbacks(remote: &RemoteOpts) -> RemoteCallbacks {
+  let mut callbacks = RemoteCallbacks::new();
+  let attempts = Cell::new(0);
+  callbacks.credentials(move |url, username_from_url, allowed| {
+    attempts.set(attempts.get() + 1);
+    if attempts.get() > 1 {
+      return Err(git2::Error::from_str(&format!("authentication failed for {}", url)));
+    }
+    if allowed.is_user_pass_plaintext() {
+      if let HttpAuthMethod::Cookie | HttpAuthMethod::Bearer = remote.http_auth {
+        return Err(git2::Error::from_str(&format!(
+          "{} authentication was not accepted by {}",
+          remote.http_auth, url
+        )));
+      }
+      let http_password = credential::http_password(remote).map_err(|e| git2::Error::from_str(&e.to_string()))?;
+      Cred::userpass_plaintext(&remote.username, &http_password)
+    } else if allowed.is_ssh_key() {
+      Cred::ssh_key_from_agent(username_from_url.unwrap_or(&remote.username))
+    } else {
+      Cred::default()
+    }
+  });
+  if remote.no_ssl_verify {
+    callbacks.certificate_check(|_cert, _host| true);
+  }
+  callbacks
+}
+
+/// Extra HTTP headers for requests to a gerrit remote outside of gerlib, e.g. by git transports,
+/// i.e. cookies for the `cookie` auth method or the token for the `bearer` auth method
+pub fn remote_headers(remote: &RemoteOpts) -> Result<Vec<String>, failure::Error> {
+  match remote.http_auth {
+    HttpAuthMethod::Cookie => {
+      let cookies = cookies::find_cookies(remote)?;
+      Ok(vec![format!("Cookie: {}", cookies::cookie_header(&cookies))])
+    }
+    HttpAuthMethod::Bearer => Ok(vec![format!(
+      "Authorization: Bearer {}",
+      credential::bearer_token(remote)?
+    )]),
+    _ => Ok(Vec::new()),
+  }
+}
+
+/// Proxy of git transports to a gerrit remote: the remote's proxy, else the one configured in git or the environment.
+/// No proxy is used for hosts excluded by the remote's `no_proxy`.
+pub fn proxy_options(remote: &RemoteOpts) -> ProxyOptions<'static> {
+  let mut options = ProxyOptions::new();
+  if remote.bypasses_proxy() {
+    return options;
+  }
+  match remote.effective_proxy() {
+    Some(proxy) => options.url(&proxy),
+    None => options.auto(),
+  };
+  options
+}
+
+/// Make git transports trust the CA bundle of a remote, in addition to the system's certificates.
+///
+/// libgit2 has no per-connection TLS settings, so this applies to the whole process.
+pub fn set_ca_bundle(remote: &RemoteOpts) -> Result<(), failure::Error> {
+  let path = match &remote.ca_bundle {
+    Some(path) => path,
+    None => return Ok(()),
+  };
+  let ca_bundle = CString::new(path.as_str())?;
+  libgit2_sys::init();
+  let code = unsafe {
+    libgit2_sys::git_libgit2_opts(
+      libgit2_sys::GIT_OPT_SET_SSL_CERT_LOCATIONS as c_int,
+      ca_bundle.as_ptr(),
+      std::ptr::null::<c_char>(),
+    )
+  };
+  if code < 0 {
+    let reason = git2::Error::last_error(code).map_or_else(String::new, |e| e.to_string());
+    return Err(failure::err_msg(format!("cannot use CA bundle {}: {}", path, reason)));
+  }
+  Ok(())
+}
+
+/// Check whether the worktree or index of a repository has uncommitted changes.
+/// Untracked files are not considered.
+pub fn is_dirty(repo: &Repository) -> Result<bool, failure::Error> {
+  let mut options = StatusOptions::new();
+  options.include_untracked(false).include_ignored(false);
+  let statuses = repo.statuses(Some(&mut options))?;
+  Ok(!statuses.is_empty())
+}
+
+/// Get the gerrit project name from a git remote URL (ssh or http).
+///
+/// e.g. `ssh://user@host:29418/my/project.git` and `https://host/a/my/project` give `my/project`.
+pub fn project_from_url(url: &str) -> Option<String> {
+  let path = match url.find("://") {
+    Some(index) => {
+      let rest = &url[index + 3..];
+      &rest[rest.find('/')? + 1..]
+    }
+    // scp-like syntax: user@host:my/project.git
+    None => &url[url.find(':')? + 1..],
+  };
+  let path = path.trim_start_matches('/').trim_end_matches('/');
+  let path = path.trim_start_matches("a/").trim_end_matches(".git");
+  if path.is_empty() {
+    None
+  } else {
+    Some(path.to_owned())
+  }
+}
+
+/// Split the path of an http git remote URL into the path gerrit is served under and the project.
+///
+/// e.g. `r/a/my/project.git` gives `/r` and `my/project`. Without an `a/` segment the served path
+/// cannot be told apart from the project, then `served_path` is used, if given, or else none is assumed.
+pub fn split_http_path(path: &str, served_path: Option<&str>) -> (String, Option<String>) {
+  let path = path.trim_matches('/');
+  let (served, rest) = match served_path.map(|p| p.trim_matches('/')) {
+    Some("") => ("", path),
+    Some(served) => match path.strip_prefix(served) {
+      Some(rest) if rest.is_empty() || rest.starts_with('/') => (served, rest),
+      _ => (served, ""),
+    },
+    None => match path.find("/a/") {
+      Some(index) if !path.starts_with("a/") => (&path[..index], &path[index + 1..]),
+      _ => ("", path),
+    },
+  };
+  let project = rest.trim_matches('/');
+  let project = project.strip_prefix("a/").unwrap_or(project).trim_end_matches(".git");
+  let served = if served.is_empty() {
+    String::new()
+  } else {
+    format!("/{}", served)
+  };
+  if project.is_empty() {
+    (served, None)
+  } else {
+    (served, Some(project.to_owned()))
+  }
+}
+
+/// Get the gerrit project of a repository, as mapped by `ger remote import` in the `ger.project` git config,
+/// or else from the URL of its 'origin' git remote.
+pub fn repo_project(repo: &Repository) -> Option<String> {
+  repo_config_str(repo, "ger.project").or_else(|| {
+    repo
+      .find_remote("origin")
+      .ok()
+      .and_then(|origin| origin.url().and_then(project_from_url))
+  })
+}
+
+/// Get the gerrit remote of a repository, as mapped by `ger remote import` in the `ger.remote` git config.
+pub fn repo_remote(repo: &Repository) -> Option<String> {
+  repo_config_str(repo, "ger.remote")
+}
+
+/// Get the gerrit project of the repository in the current directory, if any
+pub fn current_repo_project() -> Option<String> {
+  Repository::discover(std::env::current_dir().ok()?)
+    .ok()
+    .as_ref()
+    .and_then(repo_project)
+}
+
+/// Get the gerrit remote mapped to the repository in the current directory, if any
+pub fn current_repo_remote() -> Option<String> {
+  Repository::discover(std::env::current_dir().ok()?)
+    .ok()
+    .as_ref()
+    .and_then(repo_remote)
+}
+
+fn repo_config_str(repo: &Repository, name: &str) -> Option<String> {
+  repo.config().ok()?.get_string(name).ok()
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  /// Expect the project path of http, ssh and scp-like URLs
+  fn project_from_urls() {
+    assert_eq!(
+      project_from_url("https://review.example.com/a/my/project").as_deref(),
+      Some("my/project")
+    );
+    assert_eq!(
+      project_from_url("https://review.example.com/my/project.git/").as_deref(),
+      Some("my/project")
+    );
+    assert_eq!(
+      project_from_url("ssh://alice@review.example.com:29418/project.git").as_deref(),
+      Some("project")
+    );
+    assert_eq!(
+      project_from_url("alice@review.example.com:my/project.git").as_deref(),
+      Some("my/project")
+    );
+  }
+
+  #[test]
+  /// Expect the path gerrit is served under to be kept apart from the project
+  fn split_http_paths() {
+    let split = |path, served| split_http_path(path, served);
+    assert_eq!(
+      ("/r".to_owned(), Some("my/project".to_owned())),
+      split("r/a/my/project.git", None)
+    );
+    assert_eq!(
+      (String::new(), Some("my/project".to_owned())),
+      split("/a/my/project", None)
+    );
+    assert_eq!(
+      (String::new(), Some("r/my/project".to_owned())),
+      split("r/my/project", None)
+    );
+    assert_eq!(
+      ("/r".to_owned(), Some("my/project".to_owned())),
+      split("r/my/project", Some("/r/"))
+    );
+    assert_eq!(
+      (String::new(), Some("r/my/project".to_owned())),
+      split("r/my/project", Some(""))
+    );
+    assert_eq!(("/r".to_owned(), None), split("review/my/project", Some("r")));
+  }
+
+  #[test]
+  /// Expect no project for URLs without a path
+  fn project_from_url_without_path() {
+    assert_eq!(project_from_url("https://review.example.com/"), None);
+    assert_eq!(project_from_url("https://review.example.com"), None);
+    assert_eq!(project_from_url("review.example.com"), None);
+  }
+}
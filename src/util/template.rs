@@ -0,0 +1,289 @@
+use termcolor::{Color, ColorSpec, WriteColor};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// Output template, e.g. `{number:>6} {@yellow}{owner.name:<20.20}{@reset} {subject}`.
+///
+/// Fields are given in braces, optionally followed by a format spec made of an alignment
+/// (`<` left, `>` right, `^` center), a minimum width and a maximum width after a dot.
+/// Color directives are given in braces starting with `@`, combined with `+`, e.g. `{@bold+green}`,
+/// and `{@reset}` restores the default colors. Literal braces are written as `{{` and `}}`.
+#[derive(Debug, Clone)]
+pub struct Template {
+  items: Vec<Item>,
+}
+
+#[derive(Debug, Clone)]
+enum Item {
+  Literal(String),
+  Field {
+    name: String,
+    align: Align,
+    width: Option<usize>,
+    max: Option<usize>,
+  },
+  Color(ColorSpec),
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Align {
+  Left,
+  Right,
+  Center,
+}
+
+impl Template {
+  /// Parse a template string
+  pub fn parse(template: &str) -> Result<Self, failure::Error> {
+    let mut items = Vec::new();
+    let mut literal = String::new();
+    let mut chars = template.chars().peekable();
+    while let Some(c) = chars.next() {
+      match c {
+        '{' if chars.peek() == Some(&'{') => {
+          chars.next();
+          literal.push('{');
+        }
+        '}' if chars.peek() == Some(&'}') => {
+          chars.next();
+          literal.push('}');
+        }
+        '{' => {
+          let mut directive = String::new();
+          loop {
+            match chars.next() {
+              Some('}') => break,
+              Some(c) => directive.push(c),
+              None => return Err(failure::err_msg(format!("unclosed '{{' in template: {}", template))),
+            }
+          }
+          if !literal.is_empty() {
+            items.push(Item::Literal(std::mem::take(&mut literal)));
+          }
+          items.push(parse_directive(&directive)?);
+        }
+        '}' => return Err(failure::err_msg(format!("unmatched '}}' in template: {}", template))),
+        c => literal.push(c),
+      }
+    }
+    if !literal.is_empty() {
+      items.push(Item::Literal(literal));
+    }
+    Ok(Template { items })
+  }
+
+  /// Names of the fields used by the template
+  pub fn fields(&self) -> Vec<&str> {
+    self
+      .items
+      .iter()
+      .filter_map(|item| match item {
+        Item::Field { name, .. } => Some(name.as_str()),
+        _ => None,
+      })
+      .collect()
+  }
+
+  /// Render the template, getting field values from `lookup`. Unknown fields are rendered empty.
+  /// Colors are reset at the end of the output, but no newline is written.
+  pub fn render<W, F>(&self, out: &mut W, lookup: F) -> Result<(), failure::Error>
+  where
+    W: WriteColor,
+    F: Fn(&str) -> Option<String>,
+  {
+    for item in &self.items {
+      match item {
+        Item::Literal(text) => write!(out, "{}", text)?,
+        Item::Color(spec) => {
+          out.reset()?;
+          out.set_color(spec)?;
+        }
+        Item::Field {
+          name,
+          align,
+          width,
+          max,
+        } => {
+          let mut value = lookup(name).unwrap_or_default();
+          if let Some(max) = max {
+            value = truncate(&value, *max);
+          }
+          let padding = width.map(|w| w.saturating_sub(value.width())).unwrap_or(0);
+          let (left, right) = match align {
+            Align::Left => (0, padding),
+            Align::Right => (padding, 0),
+            Align::Center => (padding / 2, padding - padding / 2),
+          };
+          write!(out, "{0:1$}{2}{0:3$}", "", left, value, right)?;
+        }
+      }
+    }
+    out.reset()?;
+    Ok(())
+  }
+}
+
+/// Parse the contents of a brace pair, either a color directive or a field with optional spec
+fn parse_directive(directive: &str) -> Result<Item, failure::Error> {
+  if let Some(color) = directive.strip_prefix('@') {
+    return parse_color(color).map(Item::Color);
+  }
+
+  let (name, spec) = match directive.find(':') {
+    Some(index) => (&directive[..index], &directive[index + 1..]),
+    None => (directive, ""),
+  };
+  let name = name.trim();
+  if name.is_empty() {
+    return Err(failure::err_msg(format!(
+      "missing field name in template: {{{}}}",
+      directive
+    )));
+  }
+
+  let mut spec = spec;
+  let align = match spec.chars().next() {
+    Some('<') => Align::Left,
+    Some('>') => Align::Right,
+    Some('^') => Align::Center,
+    _ => Align::Left,
+  };
+  if let Some(rest) = spec.strip_prefix(|c| c == '<' || c == '>' || c == '^') {
+    spec = rest;
+  }
+  let (width, max) = match spec.find('.') {
+    Some(index) => (&spec[..index], &spec[index + 1..]),
+    None => (spec, ""),
+  };
+  let parse_number = |n: &str| -> Result<Option<usize>, failure::Error> {
+    match n {
+      "" => Ok(None),
+      n => n
+        .parse::<usize>()
+        .map(Some)
+        .map_err(|_| failure::err_msg(format!("invalid format spec in template: {{{}}}", directive))),
+    }
+  };
+
+  Ok(Item::Field {
+    name: name.to_owned(),
+    align,
+    width: parse_number(width)?,
+    max: parse_number(max)?,
+  })
+}
+
+/// Parse a color directive like `bold+green`
+fn parse_color(directive: &str) -> Result<ColorSpec, failure::Error> {
+  let mut spec = ColorSpec::new();
+  for attr in directive.split('+').map(|a| a.trim().to_lowercase()) {
+    match attr.as_str() {
+      "reset" => spec = ColorSpec::new(),
+      "bold" => {
+        spec.set_bold(true);
+      }
+      "italic" => {
+        spec.set_italic(true);
+      }
+      "underline" => {
+        spec.set_underline(true);
+      }
+      "intense" => {
+        spec.set_intense(true);
+      }
+      color => {
+        spec.set_fg(Some(parse_color_name(color)?));
+      }
+    }
+  }
+  Ok(spec)
+}
+
+fn parse_color_name(name: &str) -> Result<Color, failure::Error> {
+  let color = match name {
+    "black" => Color::Black,
+    "red" => Color::Red,
+    "green" => Color::Green,
+    "yellow" => Color::Yellow,
+    "blue" => Color::Blue,
+    "magenta" => Color::Magenta,
+    "cyan" => Color::Cyan,
+    "white" => Color::White,
+    _ => return Err(failure::err_msg(format!("unknown color in template: {}", name))),
+  };
+  Ok(color)
+}
+
+/// Truncate a string to a maximum display width
+fn truncate(value: &str, max: usize) -> String {
+  let mut width = 0;
+  let mut truncated = String::new();
+  for c in value.chars() {
+    width += c.width().unwrap_or(0);
+    if width > max {
+      break;
+    }
+    truncated.push(c);
+  }
+  truncated
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use termcolor::NoColor;
+
+  fn render(template: &str, lookup: impl Fn(&str) -> Option<String>) -> String {
+    let mut out = NoColor::new(Vec::new());
+    Template::parse(template).unwrap().render(&mut out, lookup).unwrap();
+    String::from_utf8(out.into_inner()).unwrap()
+  }
+
+  #[test]
+  /// Expect fields to be padded, aligned and truncated by their format spec
+  fn render_format_spec() {
+    let lookup = |name: &str| match name {
+      "number" => Some("42".to_owned()),
+      "subject" => Some("Fix the build".to_owned()),
+      _ => None,
+    };
+    assert_eq!(render("{number:>4}|{subject:.3}|", lookup), "  42|Fix|");
+    assert_eq!(render("{number:^6}|{number:<4}|", lookup), "  42  |42  |");
+  }
+
+  #[test]
+  /// Expect unknown fields to be rendered empty but still padded
+  fn render_unknown_field() {
+    assert_eq!(render("[{missing}] [{missing:3}]", |_| None), "[] [   ]");
+  }
+
+  #[test]
+  /// Expect doubled braces to be literal and color directives to produce no text
+  fn render_literal_braces_and_colors() {
+    assert_eq!(render("{{{@bold+green}x{@reset}}}", |_| None), "{x}");
+  }
+
+  #[test]
+  /// Expect the field names in template order
+  fn fields_in_order() {
+    let template = Template::parse("{a} {@red}{b.c:>3} {a}").unwrap();
+    assert_eq!(template.fields(), vec!["a", "b.c", "a"]);
+  }
+
+  #[test]
+  /// Expect unbalanced braces to be rejected
+  fn parse_unbalanced_braces() {
+    assert!(Template::parse("{number").is_err());
+    assert!(Template::parse("number}").is_err());
+    assert!(Template::parse("{number}}").is_err());
+  }
+
+  #[test]
+  /// Expect invalid directives to be rejected
+  fn parse_invalid_directive() {
+    assert!(Template::parse("{}").is_err());
+    assert!(Template::parse("{:>3}").is_err());
+    assert!(Template::parse("{number:>x}").is_err());
+    assert!(Template::parse("{number:3.x}").is_err());
+    assert!(Template::parse("{@purple}").is_err());
+  }
+}
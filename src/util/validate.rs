@@ -21,3 +21,11 @@ pub fn is_url_http_https(v: String) -> Result<(), String> {
   }
   Err(String::from("missing http:// or https://"))
 }
+
+/// Validate a string is a label vote in the form `Label=+1`
+pub fn is_label_vote(v: String) -> Result<(), String> {
+  match v.find('=') {
+    Some(i) if i > 0 && v[i + 1..].trim().parse::<i32>().is_ok() => Ok(()),
+    _ => Err(String::from("not a label vote in the form 'Label=+1'")),
+  }
+}